@@ -0,0 +1,227 @@
+use std::{borrow::Cow, env, ops::RangeInclusive, process, str::FromStr};
+
+use aoc2023::{read_input, Mode, Solution};
+
+const DAYS: RangeInclusive<u8> = 1..=5;
+
+const USAGE: &str = "Usage:
+    aoc run <day> [--part <1|2>] [--input <path|->] [--lenient]
+    aoc run --all [--lenient]";
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+
+    let command = match Command::from_args(&args) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            process::exit(2);
+        }
+    };
+
+    for day in command.days {
+        if let Err(e) = run_day(day, command.part, command.mode, command.input.as_deref()) {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    }
+}
+
+fn run_day(day: u8, part: Option<Part>, mode: Mode, path: Option<&str>) -> Result<(), String> {
+    match day {
+        1 => run::<day01::Day01>(day, &load(path, day01::INPUT)?, part, mode),
+        2 => run::<day02::Day02>(day, &load(path, day02::INPUT)?, part, mode),
+        3 => run::<day03::Day03>(day, &load(path, day03::INPUT)?, part, mode),
+        4 => run::<day04::Day04>(day, &load(path, day04::INPUT)?, part, mode),
+        5 => run::<day05::Day05>(day, &load(path, day05::INPUT)?, part, mode),
+        _ => Err(format!("Day {} has no solution", day)),
+    }
+}
+
+fn load(path: Option<&str>, bundled: &'static str) -> Result<Cow<'static, str>, String> {
+    read_input(path, bundled).map_err(|e| format!("{}: {}", path.unwrap_or("-"), e))
+}
+
+fn run<S: Solution>(day: u8, input: &str, part: Option<Part>, mode: Mode) -> Result<(), String> {
+    let parsed = S::parse_with(input, mode).map_err(|e| format!("Day {}: {}", day, e))?;
+    if !parsed.skipped.is_empty() {
+        eprintln!(
+            "Day {}: skipped {} malformed line(s)",
+            day,
+            parsed.skipped.len()
+        );
+        for e in &parsed.skipped {
+            eprintln!("{}", e);
+        }
+    }
+    let input = parsed.value;
+
+    if part != Some(Part::Two) {
+        println!("Day {:02}, part 1: {}", day, S::part1(&input));
+    }
+
+    if part != Some(Part::One) {
+        match S::part2(&input) {
+            Some(answer) => println!("Day {:02}, part 2: {}", day, answer),
+            None if part == Some(Part::Two) => {
+                return Err(format!("Day {} has no solution for part 2", day))
+            }
+            None => {}
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, PartialEq)]
+struct Command {
+    days: Vec<u8>,
+    part: Option<Part>,
+    mode: Mode,
+    input: Option<String>,
+}
+impl Command {
+    fn from_args(args: &[String]) -> Result<Self, String> {
+        let mut args = args.iter().map(String::as_str);
+
+        match args.next() {
+            Some("run") => {}
+            Some(other) => return Err(format!("Unknown command '{}'", other)),
+            None => return Err("Expected a command".into()),
+        }
+
+        let mut days = None;
+        let mut part = None;
+        let mut mode = Mode::Strict;
+        let mut input = None;
+        while let Some(arg) = args.next() {
+            match arg {
+                "--all" => days = Some(DAYS.collect()),
+                "--lenient" => mode = Mode::Lenient,
+                "--part" => {
+                    let p = args.next().ok_or("Expected a part after '--part'")?;
+                    part = Some(Part::from_str(p)?);
+                }
+                "--input" => {
+                    let path = args.next().ok_or("Expected a path after '--input'")?;
+                    input = Some(path.to_string());
+                }
+                day => {
+                    let day = day
+                        .parse::<u8>()
+                        .map_err(|_| format!("Invalid day '{}'", day))?;
+                    days = Some(vec![day]);
+                }
+            }
+        }
+
+        let days: Vec<u8> = days.ok_or("Expected a day or '--all'")?;
+        if input.is_some() && days.len() > 1 {
+            return Err("'--input' can only be used with a single day".into());
+        }
+
+        Ok(Self {
+            days,
+            part,
+            mode,
+            input,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Part {
+    One,
+    Two,
+}
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("Invalid part '{}'", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(args: &[&str]) -> Result<Command, String> {
+        Command::from_args(&args.iter().map(|s| s.to_string()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_command_from_args() {
+        assert_eq!(
+            command(&["run", "3"]),
+            Ok(Command {
+                days: vec![3],
+                part: None,
+                mode: Mode::Strict,
+                input: None
+            })
+        );
+        assert_eq!(
+            command(&["run", "3", "--part", "2"]),
+            Ok(Command {
+                days: vec![3],
+                part: Some(Part::Two),
+                mode: Mode::Strict,
+                input: None
+            })
+        );
+        assert_eq!(
+            command(&["run", "3", "--input", "other.txt"]),
+            Ok(Command {
+                days: vec![3],
+                part: None,
+                mode: Mode::Strict,
+                input: Some("other.txt".into())
+            })
+        );
+        assert_eq!(
+            command(&["run", "2", "--lenient"]),
+            Ok(Command {
+                days: vec![2],
+                part: None,
+                mode: Mode::Lenient,
+                input: None
+            })
+        );
+        assert_eq!(
+            command(&["run", "--all"]),
+            Ok(Command {
+                days: vec![1, 2, 3, 4, 5],
+                part: None,
+                mode: Mode::Strict,
+                input: None
+            })
+        );
+    }
+
+    #[test]
+    fn test_command_from_args_invalid() {
+        assert!(command(&[]).is_err());
+        assert!(command(&["walk", "3"]).is_err());
+        assert!(command(&["run"]).is_err());
+        assert!(command(&["run", "three"]).is_err());
+        assert!(command(&["run", "3", "--part"]).is_err());
+        assert!(command(&["run", "3", "--part", "3"]).is_err());
+        assert!(command(&["run", "3", "--input"]).is_err());
+        assert!(command(&["run", "--all", "--input", "other.txt"]).is_err());
+    }
+
+    #[test]
+    fn test_run_day() {
+        for day in DAYS {
+            assert_eq!(run_day(day, None, Mode::Strict, None), Ok(()));
+        }
+        assert_eq!(run_day(5, Some(Part::Two), Mode::Strict, None), Ok(()));
+        assert!(run_day(6, None, Mode::Strict, None).is_err());
+        assert!(run_day(1, None, Mode::Strict, Some("does/not/exist.txt")).is_err());
+    }
+}
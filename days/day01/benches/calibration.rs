@@ -0,0 +1,63 @@
+//! Compares part 2, which finds digits and their names with a single multi-pattern scan, against
+//! the `String::replace` approach it replaced, on the bundled input. Run with
+//! `cargo bench -p day01`.
+
+use std::{hint::black_box, time::Instant};
+
+use aoc2023::Solution;
+use day01::{Day01, INPUT};
+
+const ITERATIONS: u32 = 200;
+
+fn main() {
+    let lines = Day01::parse(INPUT).expect("Bundled input");
+
+    let replace = bench("replace", || {
+        lines
+            .iter()
+            .map(|l| replace_calibration_value(&l.text))
+            .sum()
+    });
+    let matcher = bench("matcher", || Day01::part2(&lines).expect("Part 2"));
+    assert_eq!(replace.1, matcher.1, "Sums disagree");
+    println!("speedup: {:.1}x", replace.0 / matcher.0);
+}
+
+/// Runs `f` over and over, returning the time per run in microseconds and its result.
+fn bench(name: &str, f: impl Fn() -> u32) -> (f64, u32) {
+    let start = Instant::now();
+    let mut sum = 0;
+    for _ in 0..ITERATIONS {
+        sum = black_box(f());
+    }
+
+    let per_run = start.elapsed().as_secs_f64() * 1e6 / ITERATIONS as f64;
+    println!("{:<8} {:>10.1} us/run", name, per_run);
+    (per_run, sum)
+}
+
+/// The old approach: surround every word with itself around its digit, e.g. "oneight" becomes
+/// "one1oneight8eight", then take the first and last digit.
+fn replace_calibration_value(line: &str) -> u32 {
+    const NUMBERS_AS_TEXT: &[(&str, &str)] = &[
+        ("1", "one"),
+        ("2", "two"),
+        ("3", "three"),
+        ("4", "four"),
+        ("5", "five"),
+        ("6", "six"),
+        ("7", "seven"),
+        ("8", "eight"),
+        ("9", "nine"),
+    ];
+
+    let mut line = line.to_owned();
+    for (n, t) in NUMBERS_AS_TEXT {
+        line = line.replace(t, &format!("{}{}{}", t, n, t));
+    }
+
+    let mut digits = line.chars().filter_map(|c| c.to_digit(10));
+    let first = digits.next().expect("First value");
+    let last = digits.next_back().unwrap_or(first);
+    first * 10 + last
+}
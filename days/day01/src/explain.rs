@@ -0,0 +1,126 @@
+use std::fmt;
+
+use crate::{calibration_value, Line, Token, Vocabulary};
+
+/// Which digits or words of a line make up its calibration value, see [`explain`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Explanation<'a> {
+    /// The 1-based line number.
+    pub number: usize,
+    pub line: &'a str,
+    /// The first and the last token, or `None` if the line has no calibration value.
+    pub tokens: Option<(Token, Token)>,
+}
+impl Explanation<'_> {
+    pub fn value(&self) -> Option<u32> {
+        self.tokens
+            .map(|(first, last)| calibration_value(&first, &last))
+    }
+
+    /// The line with the first token in `[]` and the last one in `<>`. The two can overlap, as
+    /// in `[on<e]ight>`.
+    pub fn highlighted(&self) -> String {
+        let Some((first, last)) = self.tokens else {
+            return self.line.to_string();
+        };
+
+        let mut marks = vec![(first.start, '['), (first.end, ']')];
+        if last != first {
+            marks.extend([(last.start, '<'), (last.end, '>')]);
+        }
+        // Closing marks go before opening ones at the same offset.
+        marks.sort_by_key(|&(offset, mark)| (offset, !matches!(mark, ']' | '>')));
+
+        let mut highlighted = String::new();
+        let mut offset = 0;
+        for (at, mark) in marks {
+            highlighted.push_str(&self.line[offset..at]);
+            highlighted.push(mark);
+            offset = at;
+        }
+        highlighted.push_str(&self.line[offset..]);
+        highlighted
+    }
+
+    fn describe(&self, f: &mut fmt::Formatter<'_>, token: &Token) -> fmt::Result {
+        write!(
+            f,
+            "{:?} ({}) at {}..{}",
+            &self.line[token.start..token.end],
+            token.kind,
+            token.start,
+            token.end
+        )
+    }
+}
+impl fmt::Display for Explanation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:>4}: {}", self.number, self.highlighted())?;
+        let Some((first, last)) = &self.tokens else {
+            return write!(f, "  no calibration value");
+        };
+
+        write!(f, "  first ")?;
+        self.describe(f, first)?;
+        write!(f, ", last ")?;
+        self.describe(f, last)?;
+        write!(f, " = {}", calibration_value(first, last))
+    }
+}
+
+/// Explains the calibration value of every line with `vocabulary`.
+pub fn explain<'a>(
+    lines: &'a [Line],
+    vocabulary: &'a Vocabulary,
+) -> impl Iterator<Item = Explanation<'a>> {
+    lines.iter().map(|line| Explanation {
+        number: line.number,
+        line: &line.text,
+        tokens: vocabulary.first_and_last(&line.text),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn explained(line: &str) -> Explanation<'_> {
+        Explanation {
+            number: 1,
+            line,
+            tokens: Vocabulary::english().first_and_last(line),
+        }
+    }
+
+    #[test]
+    fn test_highlighted() {
+        assert_eq!(explained("two1nine").highlighted(), "[two]1<nine>");
+        assert_eq!(explained("xtwone3four").highlighted(), "x[two]ne3<four>");
+        assert_eq!(explained("oneight").highlighted(), "[on<e]ight>");
+        assert_eq!(explained("one2").highlighted(), "[one]<2>");
+        assert_eq!(explained("ab7c").highlighted(), "ab[7]c");
+        assert_eq!(explained("abc").highlighted(), "abc");
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            explained("x2oneight").to_string(),
+            r#"   1: x[2]on<eight>  first "2" (digit) at 1..2, last "eight" (word) at 4..9 = 28"#
+        );
+        assert_eq!(
+            explained("abc").to_string(),
+            "   1: abc  no calibration value"
+        );
+    }
+
+    #[test]
+    fn test_explain() {
+        let lines = crate::lines("pqr3stu8vwx\n\nabc");
+        let explanations = explain(&lines, Vocabulary::english()).collect::<Vec<_>>();
+
+        assert_eq!(explanations[0].value(), Some(38));
+        assert_eq!(explanations[1].number, 3);
+        assert_eq!(explanations[1].value(), None);
+    }
+}
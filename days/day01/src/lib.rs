@@ -0,0 +1,211 @@
+use aoc2023::{numbered_lines, Mode, ParseError, Parsed, Solution};
+
+mod explain;
+mod vocabulary;
+
+pub use explain::{explain, Explanation};
+pub use vocabulary::{calibration_value, Kind, Token, Vocabulary, BUNDLED};
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+pub struct Day01;
+impl Solution for Day01 {
+    type Input = Vec<Line>;
+    type Output = u32;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        Self::parse_with(s, Mode::Strict).map(|p| p.value)
+    }
+
+    /// Lines with neither a digit nor the name of one have no calibration value in either part,
+    /// so they count as malformed. Lines with only names still count for part 2.
+    fn parse_with(s: &str, mode: Mode) -> Result<Parsed<Self::Input>, ParseError> {
+        let mut parsed = Parsed::new(Vec::new());
+        for line in lines(s) {
+            if Vocabulary::english()
+                .calibration_value(&line.text)
+                .is_some()
+            {
+                parsed.value.push(line);
+                continue;
+            }
+
+            let e = ParseError::at_end(&line.text, "a digit or a digit word").on_line(line.number);
+            match mode {
+                Mode::Lenient => parsed.skipped.push(e),
+                Mode::Strict => return Err(e),
+            }
+        }
+        Ok(parsed)
+    }
+
+    fn part1(lines: &Self::Input) -> Self::Output {
+        Calibration::digits(lines).sum
+    }
+
+    fn part2(lines: &Self::Input) -> Option<Self::Output> {
+        Some(Calibration::new(lines, part2::to_calibration_value).sum)
+    }
+}
+
+/// A non-blank line of the calibration document.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Line {
+    /// The 1-based line number.
+    pub number: usize,
+    pub text: String,
+}
+
+/// Every non-blank line of `s`, trimmed (see [`numbered_lines`]), whether it has a calibration
+/// value or not.
+pub fn lines(s: &str) -> Vec<Line> {
+    numbered_lines(s)
+        .map(|(number, text)| Line {
+            number,
+            text: text.to_string(),
+        })
+        .collect()
+}
+
+/// The sum of the calibration values of some lines, along with the numbers of the lines that
+/// don't have one.
+#[derive(Debug, Default, PartialEq)]
+pub struct Calibration {
+    pub sum: u32,
+    pub missing: Vec<usize>,
+}
+impl Calibration {
+    /// Calibrates with digits only, as in part 1.
+    pub fn digits(lines: &[Line]) -> Self {
+        Self::new(lines, part1::to_calibration_value)
+    }
+
+    /// Calibrates with digits and the words of `vocabulary`, as in part 2.
+    pub fn words(lines: &[Line], vocabulary: &Vocabulary) -> Self {
+        Self::new(lines, |l| vocabulary.calibration_value(l))
+    }
+
+    fn new(lines: &[Line], value: impl Fn(&str) -> Option<u32>) -> Self {
+        let mut calibration = Self::default();
+        for line in lines {
+            match value(&line.text) {
+                Some(value) => calibration.sum += value,
+                None => calibration.missing.push(line.number),
+            }
+        }
+        calibration
+    }
+}
+
+mod part1 {
+    pub fn to_calibration_value(line: &str) -> Option<u32> {
+        let mut iter = line.chars().flat_map(|c| c.to_digit(10).into_iter());
+        let first = iter.next()?;
+        let last = iter.last().unwrap_or(first);
+        Some(first * 10 + last)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_example() {
+            assert_eq!(to_calibration_value("1abc2"), Some(12));
+            assert_eq!(to_calibration_value("pqr3stu8vwx"), Some(38));
+            assert_eq!(to_calibration_value("a1b2c3d4e5f"), Some(15));
+            assert_eq!(to_calibration_value("treb7uchet"), Some(77));
+            assert_eq!(to_calibration_value("trebuchet"), None);
+        }
+    }
+}
+
+mod part2 {
+    use super::Vocabulary;
+
+    pub fn to_calibration_value(line: &str) -> Option<u32> {
+        Vocabulary::english().calibration_value(line)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_example() {
+            assert_eq!(to_calibration_value("two1nine"), Some(29));
+            assert_eq!(to_calibration_value("eightwothree"), Some(83));
+            assert_eq!(to_calibration_value("abcone2threexyz"), Some(13));
+            assert_eq!(to_calibration_value("xtwone3four"), Some(24));
+            assert_eq!(to_calibration_value("4nineeightseven2"), Some(42));
+            assert_eq!(to_calibration_value("zoneight234"), Some(14));
+            assert_eq!(to_calibration_value("7pqrstsixteen"), Some(76));
+        }
+
+        #[test]
+        fn test_edge_cases() {
+            assert_eq!(to_calibration_value("1"), Some(11));
+            assert_eq!(to_calibration_value("one"), Some(11));
+            assert_eq!(to_calibration_value("oneight"), Some(18));
+            assert_eq!(to_calibration_value("twone"), Some(21));
+            assert_eq!(to_calibration_value("eightwo"), Some(82));
+            assert_eq!(to_calibration_value("zero"), None);
+            assert_eq!(to_calibration_value(""), None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_with() {
+        let s = "1abc2\nabc\n\n  two3  \nfour\nxyz\n";
+
+        let e = Day01::parse(s).unwrap_err();
+        assert_eq!((e.line, e.column), (2, 4));
+        assert_eq!(e.expected, "a digit or a digit word");
+
+        let parsed = Day01::parse_with(s, Mode::Lenient).unwrap();
+        assert_eq!(
+            parsed.value.iter().map(|l| l.number).collect::<Vec<_>>(),
+            vec![1, 4, 5]
+        );
+        assert_eq!(parsed.value[1].text, "two3");
+        assert_eq!(
+            parsed.skipped.iter().map(|e| e.line).collect::<Vec<_>>(),
+            vec![2, 6]
+        );
+    }
+
+    #[test]
+    fn test_calibration_missing() {
+        let lines = lines("1abc2\nabc\ntwo3\n\nfour\n\n");
+
+        assert_eq!(
+            Calibration::digits(&lines),
+            Calibration {
+                sum: 45,
+                missing: vec![2, 5]
+            }
+        );
+        assert_eq!(
+            Calibration::words(&lines, Vocabulary::english()),
+            Calibration {
+                sum: 79,
+                missing: vec![2]
+            }
+        );
+    }
+
+    #[test]
+    fn test_result_part1() {
+        assert_eq!(Day01::part1(&Day01::parse(INPUT).unwrap()), 54632);
+    }
+
+    #[test]
+    fn test_result_part2() {
+        assert_eq!(Day01::part2(&Day01::parse(INPUT).unwrap()), Some(54019));
+    }
+}
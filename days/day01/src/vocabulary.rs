@@ -0,0 +1,252 @@
+use std::{collections::BTreeMap, fmt, str::FromStr, sync::OnceLock};
+
+use aoc2023::{
+    aho_corasick::{AhoCorasick, Match},
+    numbered_lines, ParseError,
+};
+
+/// The vocabularies that come with the crate, by language code.
+pub const BUNDLED: &[(&str, &str)] = &[
+    ("en", include_str!("../vocabularies/en.txt")),
+    ("de", include_str!("../vocabularies/de.txt")),
+    ("fr", include_str!("../vocabularies/fr.txt")),
+    ("sv", include_str!("../vocabularies/sv.txt")),
+];
+
+/// The words that count as digits in a calibration line, on top of the digits `0` to `9`
+/// themselves.
+///
+/// Vocabularies are written one `word=number` pair per line, with `#` starting a comment line:
+///
+/// ```text
+/// # German
+/// eins=1
+/// zwei=2
+/// ```
+///
+/// Words can stand for numbers of more than one digit, like `ten=10`. The calibration value
+/// takes the leading digit of the first word and the trailing digit of the last one, so it is
+/// always a two-digit number: `tenone` gives 11, `twoten` gives 20 and `ten` on its own 10.
+#[derive(Clone, Debug)]
+pub struct Vocabulary {
+    /// The value of every pattern of `matcher`.
+    values: Vec<u32>,
+    matcher: AhoCorasick,
+}
+impl Vocabulary {
+    fn new<'a>(words: impl IntoIterator<Item = (&'a str, u32)>) -> Self {
+        let digits = (0..10).map(|d| (d.to_string(), d));
+        let (patterns, values): (Vec<_>, Vec<_>) = digits
+            .chain(words.into_iter().map(|(w, v)| (w.to_string(), v)))
+            .unzip();
+
+        Self {
+            values,
+            matcher: AhoCorasick::new(patterns),
+        }
+    }
+
+    /// The puzzle's own vocabulary, `one` to `nine`.
+    pub fn english() -> &'static Vocabulary {
+        static ENGLISH: OnceLock<Vocabulary> = OnceLock::new();
+        ENGLISH.get_or_init(|| Vocabulary::bundled("en").expect("Bundled vocabulary"))
+    }
+
+    /// Looks a vocabulary up in [`BUNDLED`] by its language code.
+    pub fn bundled(language: &str) -> Option<Vocabulary> {
+        BUNDLED
+            .iter()
+            .find(|(code, _)| *code == language)
+            .map(|(_, s)| s.parse().expect("Bundled vocabulary"))
+    }
+
+    /// Combines the first and the last digit or word in `line` into a two-digit number, in a
+    /// single pass. Overlapping words like "oneight" both count. `None` if there are neither.
+    pub fn calibration_value(&self, line: &str) -> Option<u32> {
+        self.first_and_last(line)
+            .map(|(first, last)| calibration_value(&first, &last))
+    }
+
+    /// The first and the last digit or word in `line`, which are the same token if there is
+    /// only one.
+    pub fn first_and_last(&self, line: &str) -> Option<(Token, Token)> {
+        let mut matches = self.matcher.find_overlapping(line);
+        let first = matches.next()?;
+        let (first, last) = matches.fold((first, first), |(first, last), m| {
+            (
+                if m.start < first.start { m } else { first },
+                if m.start > last.start { m } else { last },
+            )
+        });
+
+        Some((self.token(first), self.token(last)))
+    }
+
+    fn token(&self, m: Match) -> Token {
+        Token {
+            start: m.start,
+            end: m.end,
+            value: self.values[m.pattern],
+            kind: if m.pattern < 10 {
+                Kind::Digit
+            } else {
+                Kind::Word
+            },
+        }
+    }
+}
+
+/// A digit or word found in a line, at the byte range `start..end`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Token {
+    pub start: usize,
+    pub end: usize,
+    pub value: u32,
+    pub kind: Kind,
+}
+
+impl Token {
+    /// The first digit of the value, e.g. 1 for `ten`.
+    pub fn leading_digit(&self) -> u32 {
+        let mut value = self.value;
+        while value >= 10 {
+            value /= 10;
+        }
+        value
+    }
+
+    /// The last digit of the value, e.g. 0 for `ten`.
+    pub fn trailing_digit(&self) -> u32 {
+        self.value % 10
+    }
+}
+
+/// The two-digit calibration value of a line with the given first and last token.
+pub fn calibration_value(first: &Token, last: &Token) -> u32 {
+    first.leading_digit() * 10 + last.trailing_digit()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    Digit,
+    Word,
+}
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Kind::Digit => write!(f, "digit"),
+            Kind::Word => write!(f, "word"),
+        }
+    }
+}
+
+impl FromStr for Vocabulary {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = BTreeMap::new();
+        for (n, line) in numbered_lines(s).filter(|(_, l)| !l.starts_with('#')) {
+            let (word, value) = line
+                .split_once('=')
+                .ok_or_else(|| ParseError::at_end(line, "'='").on_line(n))?;
+            let (word, value) = (word.trim(), value.trim());
+
+            if word.is_empty() {
+                return Err(ParseError::new(line, 0, "a word").on_line(n));
+            }
+            let value = value
+                .parse::<u32>()
+                .map_err(|_| ParseError::at(line, value, "a number").on_line(n))?;
+            if words.insert(word, value).is_some() {
+                return Err(ParseError::at(line, word, "a word that isn't defined yet").on_line(n));
+            }
+        }
+
+        Ok(Self::new(words))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled() {
+        for (code, _) in BUNDLED {
+            assert!(Vocabulary::bundled(code).is_some(), "{}", code);
+        }
+        assert!(Vocabulary::bundled("xx").is_none());
+
+        let german = Vocabulary::bundled("de").unwrap();
+        assert_eq!(german.calibration_value("xfünfzehnx"), Some(55));
+        assert_eq!(german.calibration_value("einsiebenacht"), Some(18));
+        assert_eq!(german.calibration_value("zweins"), Some(21));
+
+        let french = Vocabulary::bundled("fr").unwrap();
+        assert_eq!(french.calibration_value("troisept2huit"), Some(38));
+
+        let swedish = Vocabulary::bundled("sv").unwrap();
+        assert_eq!(swedish.calibration_value("åttatvånio"), Some(89));
+    }
+
+    #[test]
+    fn test_first_and_last() {
+        let english = Vocabulary::english();
+
+        let (first, last) = english.first_and_last("xtwone3four").unwrap();
+        assert_eq!(
+            (first.start, first.end, first.value, first.kind),
+            (1, 4, 2, Kind::Word)
+        );
+        assert_eq!((last.start, last.end, last.value), (7, 11, 4));
+
+        let (first, last) = english.first_and_last("ab7c").unwrap();
+        assert_eq!(first, last);
+        assert_eq!((first.start, first.kind), (2, Kind::Digit));
+
+        let (_, last) = Vocabulary::bundled("de")
+            .unwrap()
+            .first_and_last("1fünf")
+            .unwrap();
+        assert_eq!((last.start, last.end), (1, 6));
+
+        assert_eq!(english.first_and_last("abc"), None);
+    }
+
+    #[test]
+    fn test_extensions() {
+        let vocabulary = "# Extensions\n\nzero=0\none = 1\nten=10\nmillion=1000000\n"
+            .parse::<Vocabulary>()
+            .unwrap();
+
+        assert_eq!(vocabulary.calibration_value("zeroone"), Some(1));
+        assert_eq!(vocabulary.calibration_value("onezero"), Some(10));
+        assert_eq!(vocabulary.calibration_value("tenone"), Some(11));
+        assert_eq!(vocabulary.calibration_value("3ten"), Some(30));
+        assert_eq!(vocabulary.calibration_value("ten"), Some(10));
+        assert_eq!(vocabulary.calibration_value("million9ten"), Some(10));
+        assert_eq!(vocabulary.calibration_value("two0"), Some(0));
+        assert_eq!(vocabulary.calibration_value("two"), None);
+    }
+
+    #[test]
+    fn test_from_str_invalid() {
+        let e = "one=1\ntwo 2".parse::<Vocabulary>().unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_ref()), (2, 6, "'='"));
+
+        let e = "=1".parse::<Vocabulary>().unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_ref()), (1, 1, "a word"));
+
+        let e = "one=x".parse::<Vocabulary>().unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_ref()), (1, 5, "a number"));
+
+        let e = "big=99999999999".parse::<Vocabulary>().unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_ref()), (1, 5, "a number"));
+
+        let e = "one=1\n  one=2".parse::<Vocabulary>().unwrap_err();
+        assert_eq!(
+            (e.line, e.column, e.expected.as_ref()),
+            (2, 1, "a word that isn't defined yet")
+        );
+    }
+}
@@ -0,0 +1,232 @@
+use aoc2023::{
+    parse_lines,
+    parser::{
+        self, integer, key_value, keyword, literal, map, pair, preceded, separated, Keywords,
+        Parser,
+    },
+    prelude::*,
+    Parsed,
+};
+use std::str::FromStr;
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+pub struct Day02;
+impl Solution for Day02 {
+    type Input = Vec<Game>;
+    type Output = usize;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        Self::parse_with(s, Mode::Strict).map(|p| p.value)
+    }
+
+    fn parse_with(s: &str, mode: Mode) -> Result<Parsed<Self::Input>, ParseError> {
+        parse_lines(s, mode)
+    }
+
+    fn part1(games: &Self::Input) -> Self::Output {
+        part1::run(games)
+    }
+
+    fn part2(games: &Self::Input) -> Option<Self::Output> {
+        Some(part2::run(games))
+    }
+}
+
+mod part1 {
+    use super::*;
+
+    pub fn run(games: &[Game]) -> usize {
+        let config = Set::new(12, 13, 14);
+
+        games
+            .iter()
+            .filter(|g| g.is_possible(&config))
+            .map(|g| g.id)
+            .sum()
+    }
+}
+
+mod part2 {
+    use super::*;
+
+    pub fn run(games: &[Game]) -> usize {
+        games
+            .iter()
+            .map(|g| g.minimum_required_configuration().power())
+            .sum()
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub struct Game {
+    id: usize,
+    sets: Vec<Set>,
+}
+impl Game {
+    fn is_possible(&self, config: &Set) -> bool {
+        self.sets.iter().all(|s| s.is_possible(config))
+    }
+
+    fn minimum_required_configuration(&self) -> Set {
+        self.sets.iter().fold(Set::new(0, 0, 0), |acc, s| {
+            Set::new(s.r.max(acc.r), s.g.max(acc.g), s.b.max(acc.b))
+        })
+    }
+}
+impl FromStr for Game {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let game = key_value(
+            preceded(literal("Game"), integer()),
+            separated(set(), literal(";")),
+        );
+        let (id, sets) = parser::parse(s, game)?;
+
+        Ok(Game { id, sets })
+    }
+}
+
+#[derive(PartialEq, Debug)]
+struct Set {
+    r: usize,
+    g: usize,
+    b: usize,
+}
+impl Set {
+    fn new(r: usize, g: usize, b: usize) -> Self {
+        Self { r, g, b }
+    }
+
+    fn is_possible(&self, config: &Set) -> bool {
+        self.r <= config.r && self.g <= config.g && self.b <= config.b
+    }
+
+    fn power(&self) -> usize {
+        self.r * self.g * self.b
+    }
+}
+impl FromStr for Set {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parser::parse(s, set())
+    }
+}
+
+static COLOURS: Keywords = Keywords::new(&["red", "green", "blue"]);
+
+/// Parses a set of cubes such as `3 blue, 4 red`.
+fn set<'a>() -> impl Parser<'a, Set> {
+    let cubes = pair(integer(), keyword(&COLOURS));
+
+    map(separated(cubes, literal(",")), |cubes| {
+        cubes
+            .into_iter()
+            .fold(Set::new(0, 0, 0), |set, (n, colour)| match colour {
+                "red" => Set { r: n, ..set },
+                "green" => Set { g: n, ..set },
+                _ => Set { b: n, ..set },
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example_game1() -> Game {
+        Game {
+            id: 1,
+            sets: vec![Set::new(4, 0, 3), Set::new(1, 2, 6), Set::new(0, 2, 0)],
+        }
+    }
+
+    fn example_game2() -> Game {
+        Game {
+            id: 2,
+            sets: vec![Set::new(0, 2, 1), Set::new(1, 3, 4), Set::new(0, 1, 1)],
+        }
+    }
+
+    #[test]
+    fn test_game_from_str() {
+        assert_eq!(
+            Game::from_str("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"),
+            Ok(example_game1()),
+        );
+
+        assert_eq!(
+            Game::from_str("Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue"),
+            Ok(example_game2()),
+        )
+    }
+
+    #[test]
+    fn test_game_from_str_invalid() {
+        let e = Game::from_str("Game 1 3 blue").unwrap_err();
+        assert_eq!((e.column, e.expected.as_ref()), (8, "':'"));
+
+        let e = Game::from_str("Game 1: 3 blue; 2 purple").unwrap_err();
+        assert_eq!((e.column, e.found.as_str()), (19, "'purple'"));
+
+        let e = Game::from_str("Game 1: 3 blue; 99999999999999999999 red").unwrap_err();
+        assert_eq!((e.column, e.found.as_str()), (17, "'99999999999999999999'"));
+    }
+
+    #[test]
+    fn test_parse_strict() {
+        let e = Day02::parse("Game 1: 3 blue\n\nGame 3 3 blue\nGame 4 1 red").unwrap_err();
+        assert_eq!(e.line, 3);
+    }
+
+    #[test]
+    fn test_parse_lenient() {
+        let parsed = Day02::parse_with(
+            "Game 1: 3 blue\n\nGame 3 3 blue\nGame 4 1 red",
+            Mode::Lenient,
+        )
+        .unwrap();
+        assert_eq!(parsed.value.len(), 1);
+        assert_eq!(parsed.skipped.len(), 2);
+    }
+
+    #[test]
+    fn test_is_possible() {
+        let game = Game {
+            id: 1,
+            sets: vec![Set::new(10, 0, 20)],
+        };
+
+        assert!(game.is_possible(&Set::new(10, 10, 30)));
+        assert!(game.is_possible(&Set::new(10, 0, 30)));
+
+        assert!(!game.is_possible(&Set::new(9, 0, 50)));
+        assert!(!game.is_possible(&Set::new(0, 0, 0)));
+    }
+
+    #[test]
+    fn test_minimum_required_configuration() {
+        assert_eq!(
+            example_game1().minimum_required_configuration(),
+            Set::new(4, 2, 6)
+        )
+    }
+
+    #[test]
+    fn test_power() {
+        assert_eq!(example_game1().minimum_required_configuration().power(), 48);
+        assert_eq!(example_game2().minimum_required_configuration().power(), 12);
+    }
+
+    #[test]
+    fn test_answer_part1() {
+        assert_eq!(Day02::part1(&Day02::parse(INPUT).unwrap()), 2406);
+    }
+
+    #[test]
+    fn test_answer_part2() {
+        assert_eq!(Day02::part2(&Day02::parse(INPUT).unwrap()), Some(78375));
+    }
+}
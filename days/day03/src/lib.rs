@@ -0,0 +1,313 @@
+use aoc2023::{checked_extract_number, interval::Interval, numbered_lines, ParseError, Solution};
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+pub struct Day03;
+impl Solution for Day03 {
+    type Input = Vec<Vec<ParsedToken>>;
+    type Output = u32;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        tokenize(s)
+    }
+
+    fn part1(tokens_per_line: &Self::Input) -> Self::Output {
+        find_part_numbers(tokens_per_line).iter().sum()
+    }
+
+    fn part2(tokens_per_line: &Self::Input) -> Option<Self::Output> {
+        Some(find_gear_parts(tokens_per_line).iter().sum())
+    }
+}
+
+fn tokenize(s: &str) -> Result<Vec<Vec<ParsedToken>>, ParseError> {
+    numbered_lines(s)
+        .map(|(n, l)| {
+            LineParser::new(l)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| e.on_line(n))
+        })
+        .collect()
+}
+
+fn find_part_numbers(tokens_per_line: &[Vec<ParsedToken>]) -> Vec<u32> {
+    let mut part_numbers = Vec::new();
+    for_each_token(tokens_per_line, |row, col| {
+        let ParsedToken { ref token, .. } = tokens_per_line[row][col];
+
+        if let Token::Number(n) = token {
+            if !adjacent_tokens(row, col, tokens_per_line, |t| {
+                if matches!(t, Token::Symbol(_)) {
+                    Some(())
+                } else {
+                    None
+                }
+            })
+            .is_empty()
+            {
+                part_numbers.push(*n);
+            }
+        }
+    });
+    part_numbers
+}
+
+fn find_gear_parts(tokens_per_line: &[Vec<ParsedToken>]) -> Vec<u32> {
+    let mut part_numbers = Vec::new();
+    for_each_token(tokens_per_line, |row, col| {
+        let ParsedToken { ref token, .. } = tokens_per_line[row][col];
+
+        if let Token::Symbol('*') = token {
+            let parts = adjacent_tokens(row, col, tokens_per_line, |t| {
+                if let Token::Number(n) = t {
+                    Some(*n)
+                } else {
+                    None
+                }
+            });
+
+            if parts.len() == 2 {
+                part_numbers.push(parts[0] * parts[1]);
+            }
+        }
+    });
+    part_numbers
+}
+
+fn for_each_token(tokens_per_line: &[Vec<ParsedToken>], mut f: impl FnMut(usize, usize)) {
+    for (l, ts) in tokens_per_line.iter().enumerate() {
+        for (i, _) in ts.iter().enumerate() {
+            f(l, i);
+        }
+    }
+}
+
+fn adjacent_tokens<T>(
+    row: usize,
+    col: usize,
+    tokens_per_line: &[Vec<ParsedToken>],
+    mut f: impl FnMut(&Token) -> Option<T>,
+) -> Vec<T> {
+    let current_line_tokens = &tokens_per_line[row];
+    let current_token = &current_line_tokens[col];
+    let span = current_token.span;
+    let window = Interval::new(span.start.saturating_sub(1), span.end + 1);
+    let mut result = Vec::new();
+
+    // Above
+    if row > 0 {
+        tokens_per_line[row - 1]
+            .iter()
+            .filter(|pt| pt.span.overlaps(&window))
+            .flat_map(|pt| f(&pt.token))
+            .for_each(|c| result.push(c));
+    }
+
+    // Same line
+    if col > 0 {
+        if let Some(t) = f(&current_line_tokens[col - 1].token) {
+            result.push(t);
+        }
+    }
+    if let Some(t) = current_line_tokens.get(col + 1).and_then(|pt| f(&pt.token)) {
+        result.push(t);
+    }
+
+    // Below
+    if let Some(below) = tokens_per_line.get(row + 1) {
+        below
+            .iter()
+            .filter(|pt| pt.span.overlaps(&window))
+            .flat_map(|pt| f(&pt.token))
+            .for_each(|c| result.push(c));
+    }
+
+    result
+}
+
+struct LineParser<'a> {
+    s: &'a str,
+    i: usize,
+}
+impl LineParser<'_> {
+    fn new(s: &str) -> LineParser<'_> {
+        LineParser { s, i: 0 }
+    }
+
+    fn parse_space(&mut self) -> ParsedToken {
+        let count = self.s[self.i..].chars().take_while(|c| *c == '.').count();
+
+        let parsed_token = ParsedToken::new(self.i, count, Token::Space);
+        self.i += count;
+
+        parsed_token
+    }
+
+    fn parse_symbol(&mut self) -> ParsedToken {
+        let parsed_token = ParsedToken::new(
+            self.i,
+            1,
+            Token::Symbol(self.s.chars().nth(self.i).expect("Symbol")),
+        );
+
+        self.i += 1;
+
+        parsed_token
+    }
+
+    fn parse_number(&mut self) -> Result<ParsedToken, ParseError> {
+        let rest = &self.s[self.i..];
+        let len = rest.chars().take_while(|c| c.is_ascii_digit()).count();
+        let number = &rest[..len];
+
+        let n = checked_extract_number(number).map_err(|e| e.within(self.s, number))?;
+        let parsed_token = ParsedToken::new(self.i, len, Token::Number(n));
+        self.i += len;
+
+        Ok(parsed_token)
+    }
+}
+impl Iterator for LineParser<'_> {
+    type Item = Result<ParsedToken, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.s.chars().nth(self.i) {
+            None => None,
+            Some('.') => Some(Ok(self.parse_space())),
+            Some(n) if n.is_ascii_digit() => Some(self.parse_number()),
+            _ => Some(Ok(self.parse_symbol())),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ParsedToken {
+    span: Interval<usize>,
+    token: Token,
+}
+impl ParsedToken {
+    fn new(start: usize, len: usize, token: Token) -> Self {
+        Self {
+            span: Interval::from_len(start, len),
+            token,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Number(u32),
+    Space,
+    Symbol(char),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r#"
+    467..114..
+    ...*......
+    ..35..633.
+    ......#...
+    617*......
+    .....+.58.
+    ..592.....
+    ......755.
+    ...$.*....
+    .664.598..
+    "#;
+
+    #[test]
+    fn test_line_parser() {
+        assert_eq!(
+            LineParser::new("..11.@..33.").collect::<Result<Vec<_>, _>>(),
+            Ok(vec![
+                ParsedToken::new(0, 2, Token::Space),
+                ParsedToken::new(2, 2, Token::Number(11)),
+                ParsedToken::new(4, 1, Token::Space),
+                ParsedToken::new(5, 1, Token::Symbol('@')),
+                ParsedToken::new(6, 2, Token::Space),
+                ParsedToken::new(8, 2, Token::Number(33)),
+                ParsedToken::new(10, 1, Token::Space)
+            ])
+        );
+    }
+
+    #[test]
+    fn test_tokenize_invalid() {
+        let e = tokenize("99999999999*").unwrap_err();
+        assert_eq!((e.line, e.column), (1, 1));
+        assert_eq!(e.expected, "a number that fits in u32");
+
+        let e = Day03::parse("...\n\n..4294967296.").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (3, 3, "'4294967296'"));
+        assert!(tokenize("4294967295").is_ok());
+    }
+
+    #[test]
+    fn test_example() {
+        assert_eq!(
+            find_part_numbers(&tokenize(EXAMPLE).unwrap()),
+            vec![467, 35, 633, 617, 592, 755, 664, 598]
+        );
+    }
+
+    #[test]
+    fn test_find_part_numbers_same_line() {
+        assert_eq!(find_part_numbers(&tokenize("1").unwrap()), vec![]);
+        assert_eq!(find_part_numbers(&tokenize(".1").unwrap()), vec![]);
+        assert_eq!(find_part_numbers(&tokenize("1.").unwrap()), vec![]);
+        assert_eq!(find_part_numbers(&tokenize(".1.").unwrap()), vec![]);
+
+        assert_eq!(find_part_numbers(&tokenize("").unwrap()), vec![]);
+        assert_eq!(find_part_numbers(&tokenize("@").unwrap()), vec![]);
+        assert_eq!(find_part_numbers(&tokenize("@1").unwrap()), vec![1]);
+        assert_eq!(find_part_numbers(&tokenize("1@").unwrap()), vec![1]);
+
+        assert_eq!(find_part_numbers(&tokenize("1.@").unwrap()), vec![]);
+        assert_eq!(find_part_numbers(&tokenize("1.@.2").unwrap()), vec![]);
+        assert_eq!(find_part_numbers(&tokenize("1.@2").unwrap()), vec![2]);
+        assert_eq!(find_part_numbers(&tokenize("1@2").unwrap()), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_find_part_numbers_above() {
+        assert_eq!(find_part_numbers(&tokenize("\n").unwrap()), vec![]);
+        assert_eq!(find_part_numbers(&tokenize("\n1").unwrap()), vec![]);
+        assert_eq!(find_part_numbers(&tokenize("@..\n.1.").unwrap()), vec![1]);
+        assert_eq!(find_part_numbers(&tokenize(".@.\n.1.").unwrap()), vec![1]);
+        assert_eq!(find_part_numbers(&tokenize("..@\n.1.").unwrap()), vec![1]);
+        assert_eq!(find_part_numbers(&tokenize("...@\n.1..").unwrap()), vec![]);
+    }
+
+    #[test]
+    fn test_find_part_numbers_below() {
+        assert_eq!(find_part_numbers(&tokenize("\n").unwrap()), vec![]);
+        assert_eq!(find_part_numbers(&tokenize("1\n").unwrap()), vec![]);
+        assert_eq!(find_part_numbers(&tokenize(".1.\n@..").unwrap()), vec![1]);
+        assert_eq!(find_part_numbers(&tokenize(".1.\n.@.").unwrap()), vec![1]);
+        assert_eq!(find_part_numbers(&tokenize(".1.\n..@").unwrap()), vec![1]);
+        assert_eq!(find_part_numbers(&tokenize(".1..\n...@").unwrap()), vec![]);
+        assert_eq!(find_part_numbers(&tokenize("..1\n*..").unwrap()), vec![]);
+        assert_eq!(find_part_numbers(&tokenize("9..\n..*").unwrap()), vec![]);
+    }
+
+    #[test]
+    fn test_find_gear_parts_example() {
+        assert_eq!(
+            find_gear_parts(&tokenize(EXAMPLE).unwrap()),
+            vec![16345, 451490]
+        );
+    }
+
+    #[test]
+    fn test_result_part1() {
+        assert_eq!(Day03::part1(&Day03::parse(INPUT).unwrap()), 528799);
+    }
+
+    #[test]
+    fn test_result_part2() {
+        assert_eq!(Day03::part2(&Day03::parse(INPUT).unwrap()), Some(84907174));
+    }
+}
@@ -0,0 +1,157 @@
+use std::{collections::HashSet, str::FromStr};
+
+use aoc2023::{
+    parse_lines,
+    parser::{self, integer, key_value, literal, many, pair, preceded, terminated},
+    Mode, ParseError, Parsed, Solution,
+};
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+pub struct Day04;
+impl Solution for Day04 {
+    type Input = Vec<Card>;
+    type Output = u32;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        Self::parse_with(s, Mode::Strict).map(|p| p.value)
+    }
+
+    fn parse_with(s: &str, mode: Mode) -> Result<Parsed<Self::Input>, ParseError> {
+        parse_lines(s, mode)
+    }
+
+    fn part1(cards: &Self::Input) -> Self::Output {
+        calculate_total_points(cards)
+    }
+
+    fn part2(cards: &Self::Input) -> Option<Self::Output> {
+        Some(calculate_scratchcards(cards))
+    }
+}
+
+fn calculate_total_points(cards: &[Card]) -> u32 {
+    cards.iter().map(|c| c.points()).sum()
+}
+
+fn calculate_scratchcards(cards: &[Card]) -> u32 {
+    let mut scratchcards = vec![1; cards.len()];
+    for (i, card) in cards.iter().enumerate() {
+        for n in 0..card.matching_numbers() {
+            scratchcards[i + n + 1] += scratchcards[i];
+        }
+    }
+
+    scratchcards.iter().sum()
+}
+
+#[derive(Debug)]
+pub struct Card {
+    _number: u32,
+    winning_numbers: Vec<u32>,
+    my_numbers: Vec<u32>,
+}
+impl Card {
+    fn points(&self) -> u32 {
+        let matchin_numbers = self.matching_numbers() as u32;
+
+        if matchin_numbers == 0 {
+            0
+        } else {
+            2u32.pow(matchin_numbers - 1)
+        }
+    }
+
+    fn matching_numbers(&self) -> usize {
+        let unique_winning_numbers = self.winning_numbers.iter().copied().collect::<HashSet<_>>();
+        let unique_my_numbers = self.my_numbers.iter().copied().collect::<HashSet<_>>();
+
+        unique_winning_numbers
+            .intersection(&unique_my_numbers)
+            .count()
+    }
+}
+impl FromStr for Card {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let card = key_value(
+            preceded(literal("Card"), integer()),
+            pair(terminated(many(integer()), literal("|")), many(integer())),
+        );
+        let (number, (winning_numbers, my_numbers)) = parser::parse(s, card)?;
+
+        Ok(Card {
+            _number: number,
+            winning_numbers,
+            my_numbers,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r#"
+    Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+    Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+    Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+    Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+    Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+    Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+    "#;
+
+    #[test]
+    fn test_card_points() {
+        assert_eq!(
+            Card {
+                _number: 1,
+                winning_numbers: vec![41, 48, 83, 86, 17],
+                my_numbers: vec![83, 86, 6, 31, 17, 9, 48, 53]
+            }
+            .points(),
+            8
+        )
+    }
+
+    #[test]
+    fn test_card_from_str_invalid() {
+        let e = Card::from_str("Card 1: 41 48 83").unwrap_err();
+        assert_eq!((e.column, e.expected.as_ref()), (17, "'|'"));
+
+        let e = Card::from_str("Card 1: 41 4B 83 | 83 86").unwrap_err();
+        assert_eq!((e.column, e.found.as_str()), (12, "'4B'"));
+    }
+
+    #[test]
+    fn test_parse_lenient() {
+        let s = "Card 1: 1 2 | 1 2\nCard 2: 1 2\nCard 3: 1 | 3";
+
+        assert_eq!(Day04::parse(s).unwrap_err().line, 2);
+        assert_eq!(
+            Day04::parse_with(s, Mode::Lenient).unwrap().skipped.len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_example() {
+        assert_eq!(calculate_total_points(&Day04::parse(EXAMPLE).unwrap()), 13);
+    }
+
+    #[test]
+    fn test_example_part2() {
+        assert_eq!(calculate_scratchcards(&Day04::parse(EXAMPLE).unwrap()), 30);
+    }
+
+    #[test]
+    fn test_result_part1() {
+        assert_eq!(Day04::part1(&Day04::parse(INPUT).unwrap()), 26218);
+    }
+
+    #[test]
+    fn test_result_part2() {
+        assert_eq!(Day04::part2(&Day04::parse(INPUT).unwrap()), Some(9997537));
+    }
+}
@@ -0,0 +1,113 @@
+//! Compares looking keys up with a linear scan over a map's categories, as `Map::get` used to,
+//! against the binary search it does now. Run with `cargo bench -p day05`.
+
+use std::{fmt::Write, hint::black_box, str::FromStr, time::Instant};
+
+use day05::{Almanac, Map, INPUT};
+
+const KEYS: usize = 100_000;
+
+fn main() {
+    let bundled = Almanac::from_str(INPUT).expect("Bundled almanac");
+    let synthetic = Almanac::from_str(&synthetic_almanac(5_000)).expect("Synthetic almanac");
+
+    for (name, almanac) in [("bundled", &bundled), ("synthetic", &synthetic)] {
+        let maps = almanac.maps().collect::<Vec<_>>();
+        let categories = maps.iter().map(|m| m.categories().len()).sum::<usize>();
+        println!(
+            "{} almanac: {} maps, {} categories",
+            name,
+            maps.len(),
+            categories
+        );
+
+        let mut rng = Rng(0x2023_1205);
+        let keys = (0..KEYS)
+            .map(|_| rng.next() % (max_source(&maps) + 1))
+            .collect::<Vec<_>>();
+
+        let linear = bench("linear", &maps, &keys, linear_get);
+        let binary = bench("binary", &maps, &keys, Map::get);
+        assert_eq!(linear.1, binary.1, "Lookups disagree");
+        println!("  speedup: {:.1}x", linear.0 / binary.0);
+    }
+}
+
+fn linear_get(map: &Map, key: u64) -> u64 {
+    map.categories()
+        .iter()
+        .find_map(|c| c.get(key))
+        .unwrap_or(key)
+}
+
+/// Looks every key up in every map, returning the time per lookup in nanoseconds and a
+/// checksum of the results.
+fn bench(name: &str, maps: &[&Map], keys: &[u64], get: fn(&Map, u64) -> u64) -> (f64, u64) {
+    let start = Instant::now();
+    let mut checksum = 0u64;
+    for map in maps {
+        for key in keys {
+            checksum = checksum.wrapping_add(get(black_box(map), black_box(*key)));
+        }
+    }
+
+    let per_lookup = start.elapsed().as_nanos() as f64 / (maps.len() * keys.len()) as f64;
+    println!("  {:<8} {:>10.1} ns/lookup", name, per_lookup);
+    (per_lookup, checksum)
+}
+
+fn max_source(maps: &[&Map]) -> u64 {
+    maps.iter()
+        .flat_map(|m| m.categories())
+        .map(|c| c.source().end)
+        .max()
+        .unwrap_or_default()
+}
+
+/// An almanac with the usual seven maps, each made of `categories` adjacent source ranges of
+/// random lengths, listed in random order.
+fn synthetic_almanac(categories: usize) -> String {
+    const NAMES: [&str; 8] = [
+        "seed",
+        "soil",
+        "fertilizer",
+        "water",
+        "light",
+        "temperature",
+        "humidity",
+        "location",
+    ];
+
+    let mut rng = Rng(42);
+    let mut almanac = String::from("seeds: 79 14 55 13\n");
+    for pair in NAMES.windows(2) {
+        let mut rows = Vec::new();
+        let mut source = 0;
+        for _ in 0..categories {
+            let len = 1 + rng.next() % 100_000;
+            rows.push((rng.next() % 1_000_000_000, source, len));
+            source += len;
+        }
+        for i in (1..rows.len()).rev() {
+            rows.swap(i, (rng.next() % (i as u64 + 1)) as usize);
+        }
+
+        write!(almanac, "\n{}-to-{} map:\n", pair[0], pair[1]).unwrap();
+        for (dest, source, len) in rows {
+            writeln!(almanac, "{} {} {}", dest, source, len).unwrap();
+        }
+    }
+
+    almanac
+}
+
+/// A small xorshift generator, to keep the benchmark free of dependencies.
+struct Rng(u64);
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
@@ -0,0 +1,1290 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    error::Error,
+    fmt::{self, Write},
+    str::{FromStr, SplitWhitespace},
+};
+
+use aoc2023::{
+    interval::{Interval, IntervalSet},
+    sections, ParseError, Section, Solution,
+};
+
+pub const INPUT: &str = include_str!("../input.txt");
+
+pub struct Day05;
+impl Solution for Day05 {
+    type Input = Almanac;
+    type Output = u64;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError> {
+        Almanac::from_str(s)
+    }
+
+    fn part1(almanac: &Self::Input) -> Self::Output {
+        find_lowest_location_number(almanac)
+    }
+
+    fn part2(almanac: &Self::Input) -> Option<Self::Output> {
+        Some(find_lowest_location_number_for_ranges(almanac))
+    }
+}
+
+fn find_lowest_location_number(almanac: &Almanac) -> u64 {
+    almanac
+        .seeds
+        .iter()
+        .map(|s| almanac.seed_to_location.get(*s))
+        .min()
+        .unwrap_or_default()
+}
+
+/// Like [`find_lowest_location_number`], but with the seeds read as `(start, length)` pairs.
+/// Whole ranges are pushed through the map instead of single seeds, splitting them where they
+/// straddle category boundaries.
+fn find_lowest_location_number_for_ranges(almanac: &Almanac) -> u64 {
+    almanac
+        .seed_to_location
+        .get_ranges(&almanac.seed_ranges())
+        .min()
+        .unwrap_or_default()
+}
+
+#[derive(Debug)]
+pub struct Almanac {
+    seeds: Vec<u64>,
+    maps: BTreeMap<String, Map>,
+    /// The maps from `seed` to `location` composed into one, see [`Almanac::path`].
+    seed_to_location: Map,
+}
+impl Almanac {
+    /// The seeds read as `(start, length)` pairs, which parsing checks there is an even number
+    /// of.
+    fn seed_ranges(&self) -> IntervalSet<u64> {
+        self.seeds
+            .chunks_exact(2)
+            .map(|pair| Interval::from_len(pair[0], pair[1]))
+            .collect()
+    }
+
+    /// All maps in the almanac, by name.
+    pub fn maps(&self) -> impl Iterator<Item = &Map> {
+        self.maps.values()
+    }
+
+    /// Checks every map for values reached from more than one key, and lists the gaps between
+    /// their categories. There is nothing to report about overlapping source ranges: parsing
+    /// rejects any row that overlaps an earlier one, wherever it is in the map.
+    pub fn validate(&self) -> Report {
+        Report {
+            maps: self.maps.values().map(Map::report).collect(),
+        }
+    }
+
+    /// Composes the shortest chain of maps leading from the `from` category to the `to`
+    /// category into a single map, e.g. `soil` to `humidity`, whose [`Map::get`] looks a value
+    /// up through the whole chain at once.
+    pub fn path(&self, from: &str, to: &str) -> Result<Map, NoPath> {
+        let identity = Map::new(from, from, vec![]);
+        Ok(self
+            .chain(from, to)?
+            .into_iter()
+            .fold(identity, |map, next| map.compose(next)))
+    }
+
+    /// The shortest chain of maps leading from the `from` category to the `to` category, in
+    /// order.
+    fn chain(&self, from: &str, to: &str) -> Result<Vec<&Map>, NoPath> {
+        let mut reached_by: BTreeMap<&str, &Map> = BTreeMap::new();
+        let mut queue = VecDeque::from([from]);
+
+        while let Some(category) = queue.pop_front() {
+            if category == to {
+                let mut chain = Vec::new();
+                let mut category = to;
+                while category != from {
+                    let map = reached_by[category];
+                    chain.push(map);
+                    category = &map.source;
+                }
+
+                chain.reverse();
+                return Ok(chain);
+            }
+
+            for map in self.maps.values().filter(|m| m.source == category) {
+                if map.dest != from && !reached_by.contains_key(map.dest.as_str()) {
+                    reached_by.insert(&map.dest, map);
+                    queue.push_back(&map.dest);
+                }
+            }
+        }
+
+        Err(NoPath {
+            from: from.to_string(),
+            to: to.to_string(),
+        })
+    }
+
+    /// Follows `seed` through the maps down to its location.
+    pub fn trace(&self, seed: u64) -> Trace {
+        let chain = self
+            .chain("seed", "location")
+            .expect("Checked when parsing");
+
+        let mut value = seed;
+        let steps = chain
+            .iter()
+            .map(|map| {
+                let matched = map.find(value).map(|i| map.categories[i].clone());
+                value = map.get(value);
+                Step {
+                    map: map.name(),
+                    category: map.dest.clone(),
+                    value,
+                    matched,
+                }
+            })
+            .collect();
+
+        Trace { seed, steps }
+    }
+
+    /// Renders the categories and the ranges of every map as a Graphviz DOT digraph. With a
+    /// `seed`, the ranges it passes through on its way to a location are highlighted, and each
+    /// category is labelled with the value the seed has there.
+    pub fn to_dot(&self, seed: Option<u64>) -> String {
+        // The value of the seed in each category, and the category it matches in each map.
+        let mut values = BTreeMap::new();
+        let mut matched = BTreeMap::new();
+        if let Some(seed) = seed {
+            let trace = self.trace(seed);
+            values.insert("seed".to_string(), seed);
+            for step in trace.steps {
+                matched.insert(step.map, step.matched);
+                values.insert(step.category, step.value);
+            }
+        }
+
+        let mut nodes = String::new();
+        let mut edges = String::new();
+        let categories = self
+            .maps
+            .values()
+            .flat_map(|m| [&m.source, &m.dest])
+            .collect::<BTreeSet<_>>();
+        for category in categories {
+            match values.get(category.as_str()) {
+                Some(value) => writeln!(
+                    nodes,
+                    "    \"{}\" [shape=ellipse, label=\"{}\\n{}\", {}];",
+                    category, category, value, HIGHLIGHT
+                ),
+                None => writeln!(nodes, "    \"{}\" [shape=ellipse];", category),
+            }
+            .unwrap();
+        }
+
+        for map in self.maps.values() {
+            let name = map.name();
+            writeln!(nodes, "\n    subgraph \"cluster_{}\" {{", name).unwrap();
+            writeln!(nodes, "        label=\"{}\";", name).unwrap();
+
+            // The last row stands for the keys that no category covers.
+            let rows = map.categories.iter().map(Some).chain([None]);
+            for (i, category) in rows.enumerate() {
+                let label = match category {
+                    Some(c) => c.to_string(),
+                    None => "other keys (+0)".to_string(),
+                };
+                let taken = matched.get(&name) == Some(&category.cloned());
+                let (node_style, edge_style) = if taken {
+                    (format!(", {}", HIGHLIGHT), format!(" [{}]", HIGHLIGHT))
+                } else {
+                    Default::default()
+                };
+
+                let row = format!("{}/{}", name, i);
+                writeln!(
+                    nodes,
+                    "        \"{}\" [label=\"{}\"{}];",
+                    row, label, node_style
+                )
+                .unwrap();
+                writeln!(
+                    edges,
+                    "    \"{}\" -> \"{}\"{};",
+                    map.source, row, edge_style
+                )
+                .unwrap();
+                writeln!(edges, "    \"{}\" -> \"{}\"{};", row, map.dest, edge_style).unwrap();
+            }
+            nodes.push_str("    }\n");
+        }
+
+        format!(
+            "digraph almanac {{\n    rankdir=LR;\n    node [shape=box];\n\n{}\n{}}}\n",
+            nodes, edges
+        )
+    }
+
+    /// The inverse of the seed to location map.
+    pub fn location_to_seed(&self) -> Map {
+        self.seed_to_location.invert()
+    }
+}
+impl FromStr for Almanac {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut sections = sections(s);
+
+        let seeds_section = sections
+            .next()
+            .ok_or_else(|| ParseError::at_end("", "'seeds:'"))?;
+        let mut lines = seeds_section.numbered_lines();
+        let (n_line, line) = lines.next().expect("Sections are never empty");
+        let (key, numbers) = line
+            .split_once(':')
+            .ok_or_else(|| ParseError::at_end(line, "':'").on_line(n_line))?;
+        if key != "seeds" {
+            return Err(ParseError::at(line, key, "'seeds'").on_line(n_line));
+        }
+
+        // Whitespace-separated, like the numbers of the category rows.
+        let tokens = numbers.split_whitespace().collect::<Vec<_>>();
+        let seeds = tokens
+            .iter()
+            .map(|t| {
+                t.parse::<u64>()
+                    .map_err(|_| ParseError::at(line, t, "a seed number").on_line(n_line))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        // Part 2 reads the seeds as `(start, length)` pairs, which have to stay in u64 just like
+        // the category ranges.
+        for (pair, pair_tokens) in seeds.chunks(2).zip(tokens.chunks(2)) {
+            match (pair, pair_tokens) {
+                ([start, len], [_, len_token]) if start.checked_add(*len).is_none() => {
+                    let expected = "a seed range length that keeps the range in u64";
+                    return Err(ParseError::at(line, len_token, expected).on_line(n_line));
+                }
+                ([_], _) => {
+                    return Err(ParseError::at_end(line, "a seed range length").on_line(n_line))
+                }
+                _ => {}
+            }
+        }
+        if let Some((n, line)) = lines.next() {
+            return Err(ParseError::new(line, 0, "a blank line").on_line(n));
+        }
+
+        let mut maps = BTreeMap::new();
+        let mut end = (n_line, line);
+        for section in sections {
+            let map = Map::from_section(&section)?;
+            if maps.contains_key(&map.name()) {
+                let header = section.lines[0];
+                return Err(ParseError::new(header, 0, "a map that isn't defined yet")
+                    .on_line(section.start));
+            }
+
+            maps.insert(map.name(), map);
+            end = section
+                .numbered_lines()
+                .last()
+                .expect("Sections are never empty");
+        }
+
+        let mut almanac = Self {
+            seeds,
+            maps,
+            seed_to_location: Map::new("seed", "seed", vec![]),
+        };
+        almanac.seed_to_location = almanac.path("seed", "location").map_err(|e| {
+            let expected = format!("maps leading from '{}' to '{}'", e.from, e.to);
+            ParseError::at_end(end.1, expected).on_line(end.0)
+        })?;
+
+        Ok(almanac)
+    }
+}
+
+/// The path of a seed through the almanac, see [`Almanac::trace`].
+#[derive(Debug, PartialEq)]
+pub struct Trace {
+    pub seed: u64,
+    pub steps: Vec<Step>,
+}
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<12} {}", "seed", self.seed)?;
+        for step in &self.steps {
+            write!(f, "{:<12} {:<12}", step.category, step.value)?;
+            match &step.matched {
+                Some(c) => writeln!(f, " via {}", c)?,
+                None => writeln!(f, " unchanged")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// One map of a [`Trace`].
+#[derive(Debug, PartialEq)]
+pub struct Step {
+    /// The name of the map, e.g. `seed-to-soil`.
+    pub map: String,
+    /// The category the map leads to, e.g. `soil`.
+    pub category: String,
+    pub value: u64,
+    /// The category row that mapped the value, or `None` if it was left unchanged.
+    pub matched: Option<Category>,
+}
+
+/// The DOT attributes for the path of the seed in [`Almanac::to_dot`].
+const HIGHLIGHT: &str = "color=red, fontcolor=red, penwidth=2";
+
+/// The result of [`Almanac::validate`].
+#[derive(Debug, PartialEq)]
+pub struct Report {
+    pub maps: Vec<MapReport>,
+}
+impl Report {
+    /// Whether no map sends two keys to the same value.
+    pub fn is_valid(&self) -> bool {
+        self.maps.iter().all(|m| m.collisions.is_empty())
+    }
+}
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for map in &self.maps {
+            write!(f, "{}", map)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct MapReport {
+    pub name: String,
+    pub collisions: Vec<Collision>,
+    /// The key ranges below the end of the last category that no category covers, and which
+    /// therefore map to themselves.
+    pub gaps: Vec<Interval<u64>>,
+}
+impl fmt::Display for MapReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.collisions.len() {
+            0 => writeln!(f, "{}: ok", self.name)?,
+            1 => writeln!(f, "{}: 1 collision", self.name)?,
+            n => writeln!(f, "{}: {} collisions", self.name, n)?,
+        }
+        for c in &self.collisions {
+            writeln!(
+                f,
+                "  values {:?} come from both {:?} and {:?}",
+                c.values, c.sources[0], c.sources[1]
+            )?;
+        }
+        for gap in &self.gaps {
+            writeln!(f, "  gap {:?}", gap)?;
+        }
+        Ok(())
+    }
+}
+
+/// Values that keys from two different source ranges map to.
+#[derive(Debug, PartialEq)]
+pub struct Collision {
+    pub values: Interval<u64>,
+    pub sources: [Interval<u64>; 2],
+}
+
+/// The error returned by [`Almanac::path`] when no chain of maps leads from one category to
+/// another.
+#[derive(Debug, PartialEq, Eq)]
+pub struct NoPath {
+    pub from: String,
+    pub to: String,
+}
+impl fmt::Display for NoPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "no maps lead from '{}' to '{}'", self.from, self.to)
+    }
+}
+impl Error for NoPath {}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Category {
+    source: Interval<u64>,
+    dest: u64,
+}
+impl Category {
+    fn new(source: u64, dest: u64, len: u64) -> Self {
+        Self {
+            source: Interval::from_len(source, len),
+            dest,
+        }
+    }
+
+    /// The keys this category maps.
+    pub fn source(&self) -> Interval<u64> {
+        self.source
+    }
+
+    pub fn get(&self, key: u64) -> Option<u64> {
+        self.source
+            .contains(key)
+            .then(|| self.dest + (key - self.source.start))
+    }
+
+    /// The values that the source range maps to.
+    fn image(&self) -> Interval<u64> {
+        self.source.shift(self.source.start, self.dest)
+    }
+
+    /// Splits `range` into the part covered by this category, mapped to its destination, and
+    /// the parts before and after it, which are left as is.
+    fn split(&self, range: &Interval<u64>) -> (Option<Interval<u64>>, IntervalSet<u64>) {
+        let mapped = range
+            .intersect(&self.source)
+            .map(|overlap| overlap.shift(self.source.start, self.dest));
+
+        (mapped, range.subtract(&self.source))
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let offset = self.dest as i128 - self.source.start as i128;
+        write!(f, "{:?} -> {:?} ({:+})", self.source, self.image(), offset)
+    }
+}
+
+/// A piecewise-linear map from one category (e.g. `seed`) to another (e.g. `soil`), with its
+/// categories sorted by source range.
+#[derive(Clone, Debug)]
+pub struct Map {
+    source: String,
+    dest: String,
+    categories: Vec<Category>,
+}
+impl Map {
+    fn new(source: &str, dest: &str, mut categories: Vec<Category>) -> Self {
+        categories.sort_by_key(|c| c.source.start);
+        Self {
+            source: source.to_string(),
+            dest: dest.to_string(),
+            categories,
+        }
+    }
+
+    /// The name of the map as written in the almanac, e.g. `seed-to-soil`.
+    pub fn name(&self) -> String {
+        format!("{}-to-{}", self.source, self.dest)
+    }
+
+    pub fn get(&self, key: u64) -> u64 {
+        self.find(key)
+            .and_then(|i| self.categories[i].get(key))
+            .unwrap_or(key)
+    }
+
+    /// The index of the category covering `key`, if any.
+    fn find(&self, key: u64) -> Option<usize> {
+        let i = self.categories.partition_point(|c| c.source.start <= key);
+        i.checked_sub(1)
+            .filter(|i| self.categories[*i].source.contains(key))
+    }
+
+    /// The categories of the map, sorted by source range. Their source ranges are never empty and
+    /// never overlap.
+    pub fn categories(&self) -> &[Category] {
+        &self.categories
+    }
+
+    /// Splits the whole key space into disjoint categories, filling the gaps between this map's
+    /// categories with ones that map keys to themselves.
+    fn pieces(&self) -> Vec<Category> {
+        let mut covered = IntervalSet::new();
+        let mut pieces = Vec::new();
+        for category in &self.categories {
+            let source = IntervalSet::from_iter([category.source]);
+            pieces.extend(source.subtract(&covered).iter().map(|part| Category {
+                source: *part,
+                dest: category.dest + (part.start - category.source.start),
+            }));
+            covered.insert(category.source);
+        }
+
+        let everything = IntervalSet::from_iter([Interval::new(0, u64::MAX)]);
+        pieces.extend(everything.subtract(&covered).iter().map(|gap| Category {
+            source: *gap,
+            dest: gap.start,
+        }));
+        pieces.sort_by_key(|c| c.source.start);
+        pieces
+    }
+
+    fn report(&self) -> MapReport {
+        let mut pieces = self.pieces();
+        pieces.sort_by_key(|p| p.dest);
+
+        // Sweep over the images in order, keeping the ones that are still open.
+        let mut collisions = Vec::new();
+        let mut open: Vec<&Category> = Vec::new();
+        for piece in &pieces {
+            open.retain(|o| o.image().end > piece.dest);
+            for o in &open {
+                if let Some(values) = o.image().intersect(&piece.image()) {
+                    let mut sources = [o.source, piece.source];
+                    sources.sort_by_key(|s| s.start);
+                    collisions.push(Collision { values, sources });
+                }
+            }
+            open.push(piece);
+        }
+        collisions.sort_by_key(|c| (c.values.start, c.sources[0].start));
+
+        let end = self.categories.iter().map(|c| c.source.end).max();
+        let covered = self.categories.iter().map(|c| c.source).collect();
+        let gaps = IntervalSet::from_iter([Interval::new(0, end.unwrap_or_default())])
+            .subtract(&covered)
+            .iter()
+            .copied()
+            .collect();
+
+        MapReport {
+            name: self.name(),
+            collisions,
+            gaps,
+        }
+    }
+
+    /// Composes this map with `next`, giving a single map that sends every key where `next`
+    /// would send this map's value for it. A `seed-to-soil` map composed with a
+    /// `soil-to-fertilizer` map is a `seed-to-fertilizer` map.
+    fn compose(&self, next: &Map) -> Map {
+        let next_pieces = next.pieces();
+        let mut categories: Vec<Category> = Vec::new();
+
+        for piece in self.pieces() {
+            let image = piece.image();
+            for next_piece in &next_pieces {
+                let Some(overlap) = image.intersect(&next_piece.source) else {
+                    continue;
+                };
+                let category = Category {
+                    source: overlap.shift(piece.dest, piece.source.start),
+                    dest: next_piece.dest + (overlap.start - next_piece.source.start),
+                };
+                if category.source.start != category.dest {
+                    categories.push(category);
+                }
+            }
+        }
+
+        categories.sort_by_key(|c| c.source.start);
+        categories.dedup_by(|c, prev| {
+            let adjacent =
+                prev.source.end == c.source.start && prev.dest + prev.source.len() == c.dest;
+            if adjacent {
+                prev.source = Interval::new(prev.source.start, c.source.end);
+            }
+            adjacent
+        });
+
+        Map::new(&self.source, &next.dest, categories)
+    }
+
+    /// The map from destinations back to sources. This is only a true inverse if every value
+    /// comes from exactly one key, as in the almanac: otherwise the smallest key wins, and values
+    /// that no key maps to map to themselves.
+    pub fn invert(&self) -> Map {
+        let mut covered = IntervalSet::new();
+        let mut categories = Vec::new();
+        for piece in self.pieces() {
+            let image = IntervalSet::from_iter([piece.image()]);
+            for part in image.subtract(&covered).iter() {
+                let category = Category {
+                    source: *part,
+                    dest: piece.source.start + (part.start - piece.dest),
+                };
+                if category.source.start != category.dest {
+                    categories.push(category);
+                }
+            }
+            covered.insert(piece.image());
+        }
+
+        Map::new(&self.dest, &self.source, categories)
+    }
+
+    /// Maps every value in `ranges`. Values not covered by any category map to themselves.
+    fn get_ranges(&self, ranges: &IntervalSet<u64>) -> IntervalSet<u64> {
+        let mut mapped = IntervalSet::new();
+        let mut unmapped = ranges.clone();
+        for category in &self.categories {
+            let mut rest = IntervalSet::new();
+            for range in unmapped.iter() {
+                let (overlap, remaining) = category.split(range);
+                mapped.extend(overlap);
+                rest.extend(remaining.iter().copied());
+            }
+            unmapped = rest;
+        }
+
+        mapped.extend(unmapped.iter().copied());
+        mapped
+    }
+
+    /// Parses a map header followed by its category rows.
+    fn from_section(section: &Section) -> Result<Self, ParseError> {
+        let mut lines = section.numbered_lines();
+        let (n, header) = lines.next().expect("Sections are never empty");
+        let (name, rest) = header
+            .split_once(' ')
+            .ok_or_else(|| ParseError::at_end(header, "' map:'").on_line(n))?;
+        if rest != "map:" {
+            return Err(ParseError::at(header, rest, "'map:'").on_line(n));
+        }
+        let (source, dest) = name
+            .split_once("-to-")
+            .filter(|(source, dest)| !source.is_empty() && !dest.is_empty())
+            .ok_or_else(|| ParseError::at(header, name, "a name like 'seed-to-soil'").on_line(n))?;
+
+        let mut rows = Vec::new();
+        for (n, line) in lines {
+            let mut numbers = line.split_whitespace();
+            let parse_error = |e: ParseError| e.on_line(n);
+            let dest: u64 = parse_number(line, &mut numbers, "a destination range start")
+                .map_err(parse_error)?;
+            let source: u64 =
+                parse_number(line, &mut numbers, "a source range start").map_err(parse_error)?;
+            let len: u64 =
+                parse_number(line, &mut numbers, "a range length").map_err(parse_error)?;
+
+            // Both ranges are half-open, so they have to end at `u64::MAX` at the latest.
+            let len_str = line.split_whitespace().nth(2).expect("Parsed above");
+            if len == 0 {
+                return Err(ParseError::at(line, len_str, "a range length above 0").on_line(n));
+            }
+            for (start, range) in [(source, "source"), (dest, "destination")] {
+                if start.checked_add(len).is_none() {
+                    let expected = format!("a range length that keeps the {} range in u64", range);
+                    return Err(ParseError::at(line, len_str, expected).on_line(n));
+                }
+            }
+
+            rows.push((n, line, Category::new(source, dest, len)));
+        }
+
+        // Lookups binary search on the source ranges, which only works if they don't overlap.
+        // Each row is checked against the one reaching furthest so far, not just its neighbour.
+        rows.sort_by_key(|(_, _, c)| c.source.start);
+        let mut furthest: Option<(usize, Interval<u64>)> = None;
+        for (n, line, c) in &rows {
+            if let Some((previous, _)) = furthest.filter(|(_, r)| r.overlaps(&c.source)) {
+                let source = line.split_whitespace().nth(1).expect("Parsed above");
+                let expected = format!(
+                    "a source range that doesn't overlap the one on line {}",
+                    previous
+                );
+                return Err(ParseError::at(line, source, expected).on_line(*n));
+            }
+            if furthest.is_none_or(|(_, r)| c.source.end > r.end) {
+                furthest = Some((*n, c.source));
+            }
+        }
+
+        let categories = rows.into_iter().map(|(_, _, c)| c).collect();
+        Ok(Map::new(source, dest, categories))
+    }
+}
+
+impl fmt::Display for Map {
+    /// Writes the map in the almanac's own format, so that it can be parsed back.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} map:", self.name())?;
+        for c in &self.categories {
+            writeln!(f, "{} {} {}", c.dest, c.source.start, c.source.len())?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_number<T: FromStr>(
+    line: &str,
+    numbers: &mut SplitWhitespace,
+    expected: &'static str,
+) -> Result<T, ParseError> {
+    let number = numbers
+        .next()
+        .ok_or_else(|| ParseError::at_end(line, expected))?;
+
+    number
+        .parse()
+        .map_err(|_| ParseError::at(line, number, expected))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Answers part 2 from the other end: walks locations upwards from 0, a whole piece of the
+    /// inverted map at a time, until one of them comes from a seed.
+    fn find_lowest_location_number_by_search(almanac: &Almanac) -> Option<u64> {
+        let seeds = almanac.seed_ranges();
+
+        almanac
+            .location_to_seed()
+            .pieces()
+            .iter()
+            .find_map(|piece| {
+                let from_seeds = IntervalSet::from_iter([piece.image()]).intersect(&seeds);
+                let seed = from_seeds.min()?;
+                Some(piece.source.start + (seed - piece.dest))
+            })
+    }
+
+    const EXAMPLE: &str = r#"
+    seeds: 79 14 55 13
+
+    seed-to-soil map:
+    50 98 2
+    52 50 48
+
+    soil-to-fertilizer map:
+    0 15 37
+    37 52 2
+    39 0 15
+
+    fertilizer-to-water map:
+    49 53 8
+    0 11 42
+    42 0 7
+    57 7 4
+
+    water-to-light map:
+    88 18 7
+    18 25 70
+
+    light-to-temperature map:
+    45 77 23
+    81 45 19
+    68 64 13
+
+    temperature-to-humidity map:
+    0 69 1
+    1 0 69
+
+    humidity-to-location map:
+    60 56 37
+    56 93 4"#;
+
+    #[test]
+    fn test_example() {
+        let almanac = Almanac::from_str(EXAMPLE).unwrap();
+
+        assert_eq!(almanac.seeds, vec![79, 14, 55, 13]);
+        assert_eq!(almanac.maps["seed-to-soil"].get(0), 0);
+        assert_eq!(almanac.maps["seed-to-soil"].get(49), 49);
+        assert_eq!(almanac.maps["seed-to-soil"].get(50), 52);
+    }
+
+    #[test]
+    fn test_almanac_from_str_invalid() {
+        let e = Almanac::from_str("\n  seed: 1 2").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (2, 1, "'seed:'"));
+
+        let e = Almanac::from_str("seeds: 1 4294967296 99999999999999999999").unwrap_err();
+        assert_eq!((e.line, e.column), (1, 21));
+
+        let e = Almanac::from_str("seeds: 79 x 14").unwrap_err();
+        assert_eq!(
+            (e.line, e.column, e.expected.as_ref()),
+            (1, 11, "a seed number")
+        );
+
+        let e = Almanac::from_str("seeds: 7a9").unwrap_err();
+        assert_eq!((e.column, e.found.as_str()), (8, "'7a9'"));
+
+        let e = Almanac::from_str("seeds: 1,4").unwrap_err();
+        assert_eq!((e.column, e.found.as_str()), (8, "'1,4'"));
+
+        let e = Almanac::from_str("seeds:1 2 3\n\nseed-to-soil map:\n1 2 3").unwrap_err();
+        assert_eq!(
+            (e.line, e.column, e.expected.as_ref()),
+            (1, 12, "a seed range length")
+        );
+
+        let e = Almanac::from_str("seeds: 1 2\n\nseed-to-soil:\n1 2 3").unwrap_err();
+        assert_eq!((e.line, e.column), (3, 14));
+
+        let e = Almanac::from_str("seeds: 1 2\n\nseed-to-soil map:\n1 2 3\n4 x 6").unwrap_err();
+        assert_eq!(
+            (e.line, e.column, e.expected.as_ref()),
+            (5, 3, "a source range start")
+        );
+
+        let e = Almanac::from_str("seeds: 1 2\n3 4\n\nseed-to-soil map:\n1 2 3").unwrap_err();
+        assert_eq!(
+            (e.line, e.column, e.expected.as_ref()),
+            (2, 1, "a blank line")
+        );
+
+        let e = Almanac::from_str("seeds: 1 2\n\nseed-to-soil map:\n1 2").unwrap_err();
+        assert_eq!(
+            (e.line, e.column, e.expected.as_ref()),
+            (4, 4, "a range length")
+        );
+    }
+
+    #[test]
+    fn test_almanac_from_str_overlapping() {
+        let e = Almanac::from_str("seeds: 1 1\n\nseed-to-soil map:\n1 10 5\n1 20 5\n1 14 2")
+            .unwrap_err();
+        assert_eq!(
+            (e.line, e.column, e.expected.as_ref()),
+            (
+                6,
+                3,
+                "a source range that doesn't overlap the one on line 4"
+            )
+        );
+
+        // Nested inside the first row.
+        let e = Almanac::from_str("seeds: 1 1\n\nseed-to-soil map:\n1 10 100\n1000 60 5\n7 50 1")
+            .unwrap_err();
+        assert_eq!((e.line, e.column), (6, 3));
+        assert!(e.expected.ends_with("line 4"));
+
+        let e = Almanac::from_str("seeds: 1 1\n\nseed-to-soil map:\n1 10 100\n7 50 0\n1000 60 5")
+            .unwrap_err();
+        assert_eq!(
+            (e.line, e.column, e.expected.as_ref()),
+            (5, 6, "a range length above 0")
+        );
+
+        assert!(Almanac::from_str(
+            "seeds: 1 1\n\nseed-to-soil map:\n1 10 5\n1 15 5\n\nsoil-to-location map:"
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_almanac_from_str_u64_limits() {
+        let max = u64::MAX;
+        let almanac = Almanac::from_str(&format!(
+            "seeds: {} 1 {} 20\n\nseed-to-location map:\n0 {} 10\n{} 0 10",
+            max - 1,
+            max - 20,
+            max - 10,
+            max - 10
+        ))
+        .unwrap();
+        let map = &almanac.maps["seed-to-location"];
+
+        assert_eq!(map.get(max - 10), 0);
+        assert_eq!(map.get(max - 1), 9);
+        assert_eq!(map.get(max), max);
+        assert_eq!(map.get(9), max - 1);
+        assert_eq!(map.invert().get(max - 1), 9);
+        assert!(almanac.validate().is_valid());
+        assert_eq!(Day05::part1(&almanac), 9);
+        assert_eq!(Day05::part2(&almanac), Some(0));
+        assert_eq!(
+            almanac.seed_ranges(),
+            IntervalSet::from_iter([Interval::new(max - 20, max)])
+        );
+
+        let e = Almanac::from_str(&format!("seeds: 1 1 {} 2", max - 1)).unwrap_err();
+        assert_eq!(
+            (e.line, e.column, e.expected.as_ref()),
+            (1, 33, "a seed range length that keeps the range in u64")
+        );
+
+        let e = Almanac::from_str(&format!(
+            "seeds: 1 1\n\nseed-to-location map:\n0 {} 11",
+            max - 10
+        ))
+        .unwrap_err();
+        assert_eq!(
+            (e.line, e.column, e.expected.as_ref()),
+            (4, 24, "a range length that keeps the source range in u64")
+        );
+
+        let e = Almanac::from_str(&format!(
+            "seeds: 1 1\n\nseed-to-location map:\n{} 0 11",
+            max - 10
+        ))
+        .unwrap_err();
+        assert_eq!(
+            e.expected,
+            "a range length that keeps the destination range in u64"
+        );
+
+        let e = Almanac::from_str("seeds: 1 1\n\nseed-to-location map:\n0 1 18446744073709551616")
+            .unwrap_err();
+        assert_eq!(
+            (e.line, e.column, e.expected.as_ref()),
+            (4, 5, "a range length")
+        );
+    }
+
+    #[test]
+    fn test_almanac_from_str_invalid_names() {
+        let e = Almanac::from_str("seeds: 1 2\n\nseed-soil map:\n1 2 3").unwrap_err();
+        assert_eq!(
+            (e.line, e.column, e.expected.as_ref()),
+            (3, 1, "a name like 'seed-to-soil'")
+        );
+
+        let e = Almanac::from_str("seeds: 1 1\n\nseed-to-soil map:\n\nseed-to-soil map:\n1 2 3")
+            .unwrap_err();
+        assert_eq!(
+            (e.line, e.column, e.expected.as_ref()),
+            (5, 1, "a map that isn't defined yet")
+        );
+
+        let e =
+            Almanac::from_str("seeds: 1 1\n\nseed-to-soil map:\n1 2 3\n\nwater-to-location map:")
+                .unwrap_err();
+        assert_eq!(
+            (e.line, e.expected.as_ref()),
+            (6, "maps leading from 'seed' to 'location'")
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let report = Almanac::from_str(EXAMPLE).unwrap().validate();
+
+        assert!(report.is_valid());
+        assert_eq!(report.maps.len(), 7);
+        let seed_to_soil = report
+            .maps
+            .iter()
+            .find(|m| m.name == "seed-to-soil")
+            .unwrap();
+        assert_eq!(seed_to_soil.gaps, vec![Interval::new(0, 50)]);
+        assert!(Day05::parse(INPUT).unwrap().validate().is_valid());
+    }
+
+    #[test]
+    fn test_validate_overlaps() {
+        // Overlapping neighbours, a row nested in an earlier one, a row only the first one
+        // reaches, an empty row inside another one and two rows starting at the same key.
+        for rows in [
+            "1 10 5\n1 14 5",
+            "1 10 100\n7 50 1",
+            "1 10 100\n1 20 5\n1000 60 5",
+            "1 10 100\n7 50 0\n1000 60 5",
+            "1 10 5\n2 10 1",
+        ] {
+            let s = format!("seeds: 1 1\n\nseed-to-location map:\n{}", rows);
+            assert!(Almanac::from_str(&s).is_err(), "{:?}", rows);
+        }
+
+        for map in Day05::parse(INPUT).unwrap().maps() {
+            for (i, a) in map.categories().iter().enumerate() {
+                for b in &map.categories()[i + 1..] {
+                    assert!(!a.source().overlaps(&b.source()), "{}", map.name());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_collisions() {
+        let almanac =
+            Almanac::from_str("seeds: 1 1\n\nseed-to-location map:\n10 0 5\n0 10 5\n12 20 5")
+                .unwrap();
+        let report = almanac.validate();
+
+        assert!(!report.is_valid());
+        assert_eq!(
+            report.maps[0].collisions,
+            vec![
+                Collision {
+                    values: Interval::new(12, 15),
+                    sources: [Interval::new(0, 5), Interval::new(20, 25)]
+                },
+                Collision {
+                    values: Interval::new(15, 17),
+                    sources: [Interval::new(15, 20), Interval::new(20, 25)]
+                },
+            ]
+        );
+        assert_eq!(
+            report.maps[0].gaps,
+            vec![Interval::new(5, 10), Interval::new(15, 20)]
+        );
+        assert_eq!(
+            report.to_string(),
+            "seed-to-location: 2 collisions\n  \
+             values 12..15 come from both 0..5 and 20..25\n  \
+             values 15..17 come from both 15..20 and 20..25\n  \
+             gap 5..10\n  gap 15..20\n"
+        );
+    }
+
+    #[test]
+    fn test_trace() {
+        let almanac = Almanac::from_str(EXAMPLE).unwrap();
+        let trace = almanac.trace(79);
+
+        assert_eq!(
+            trace
+                .steps
+                .iter()
+                .map(|s| (s.category.as_str(), s.value))
+                .collect::<Vec<_>>(),
+            vec![
+                ("soil", 81),
+                ("fertilizer", 81),
+                ("water", 81),
+                ("light", 74),
+                ("temperature", 78),
+                ("humidity", 78),
+                ("location", 82)
+            ]
+        );
+        assert_eq!(trace.steps[0].map, "seed-to-soil");
+        assert_eq!(trace.steps[0].matched, Some(Category::new(50, 52, 48)));
+        assert_eq!(trace.steps[1].matched, None);
+        assert_eq!(
+            trace.to_string().lines().take(3).collect::<Vec<_>>(),
+            vec![
+                "seed         79",
+                "soil         81           via 50..98 -> 52..100 (+2)",
+                "fertilizer   81           unchanged",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let almanac = Almanac::from_str(EXAMPLE).unwrap();
+        let dot = almanac.to_dot(None);
+
+        assert!(dot.starts_with("digraph almanac {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert!(dot.contains("    \"seed\" [shape=ellipse];\n"));
+        assert!(dot.contains("    subgraph \"cluster_seed-to-soil\" {\n"));
+        assert!(dot.contains("        \"seed-to-soil/0\" [label=\"50..98 -> 52..100 (+2)\"];\n"));
+        assert!(dot.contains("        \"seed-to-soil/1\" [label=\"98..100 -> 50..52 (-48)\"];\n"));
+        assert!(dot.contains("        \"seed-to-soil/2\" [label=\"other keys (+0)\"];\n"));
+        assert!(dot.contains("    \"seed\" -> \"seed-to-soil/1\";\n"));
+        assert!(dot.contains("    \"seed-to-soil/1\" -> \"soil\";\n"));
+        assert!(!dot.contains(HIGHLIGHT));
+    }
+
+    #[test]
+    fn test_to_dot_highlighted() {
+        let almanac = Almanac::from_str(EXAMPLE).unwrap();
+        let dot = almanac.to_dot(Some(79));
+        let highlighted = |line: &str| dot.contains(&format!("{}, {}];\n", line, HIGHLIGHT));
+
+        assert!(highlighted(
+            "    \"soil\" [shape=ellipse, label=\"soil\\n81\""
+        ));
+        assert!(highlighted(
+            "    \"location\" [shape=ellipse, label=\"location\\n82\""
+        ));
+        assert!(highlighted(
+            "        \"seed-to-soil/0\" [label=\"50..98 -> 52..100 (+2)\""
+        ));
+        assert!(highlighted(
+            "        \"soil-to-fertilizer/3\" [label=\"other keys (+0)\""
+        ));
+        assert!(dot.contains(&format!(
+            "    \"seed\" -> \"seed-to-soil/0\" [{}];\n",
+            HIGHLIGHT
+        )));
+        assert!(dot.contains("    \"seed\" -> \"seed-to-soil/1\";\n"));
+        // One node per category and one row and two edges per map.
+        assert_eq!(dot.matches(HIGHLIGHT).count(), 8 + 7 * 3);
+    }
+
+    #[test]
+    fn test_path() {
+        let almanac = Almanac::from_str(EXAMPLE).unwrap();
+        let soil_to_humidity = almanac.path("soil", "humidity").unwrap();
+
+        assert_eq!(soil_to_humidity.name(), "soil-to-humidity");
+        assert_eq!(soil_to_humidity.get(81), 78);
+        assert_eq!(almanac.path("water", "water").unwrap().get(5), 5);
+        assert_eq!(
+            almanac.path("location", "seed").unwrap_err(),
+            NoPath {
+                from: "location".into(),
+                to: "seed".into()
+            }
+        );
+        assert!(almanac.path("seed", "sunlight").is_err());
+    }
+
+    #[test]
+    fn test_example_part1() {
+        assert_eq!(
+            find_lowest_location_number(&Almanac::from_str(EXAMPLE).unwrap()),
+            35
+        );
+    }
+
+    fn set(ranges: &[std::ops::Range<u64>]) -> IntervalSet<u64> {
+        ranges.iter().cloned().map(Interval::from).collect()
+    }
+
+    #[test]
+    fn test_category_get() {
+        let category = Category::new(98, 50, 2);
+
+        assert_eq!(category.get(97), None);
+        assert_eq!(category.get(98), Some(50));
+        assert_eq!(category.get(99), Some(51));
+        assert_eq!(category.get(100), None);
+    }
+
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn test_category_split() {
+        let category = Category::new(10, 100, 5);
+
+        assert_eq!(category.split(&(0..5).into()), (None, set(&[0..5])));
+        assert_eq!(
+            category.split(&(8..12).into()),
+            (Some((100..102).into()), set(&[8..10]))
+        );
+        assert_eq!(
+            category.split(&(0..20).into()),
+            (Some((100..105).into()), set(&[0..10, 15..20]))
+        );
+        assert_eq!(
+            category.split(&(12..20).into()),
+            (Some((102..105).into()), set(&[15..20]))
+        );
+        assert_eq!(category.split(&(15..20).into()), (None, set(&[15..20])));
+    }
+
+    #[test]
+    fn test_get_ranges() {
+        let almanac = Almanac::from_str(EXAMPLE).unwrap();
+        let ranges = almanac.maps["seed-to-soil"].get_ranges(&set(&[45..55, 97..101]));
+
+        assert_eq!(ranges, set(&[45..57, 99..101]));
+    }
+
+    #[test]
+    fn test_compose() {
+        let almanac = Almanac::from_str(EXAMPLE).unwrap();
+        let (first, second) = (
+            &almanac.maps["seed-to-soil"],
+            &almanac.maps["soil-to-fertilizer"],
+        );
+        let composed = first.compose(second);
+
+        assert_eq!(composed.name(), "seed-to-fertilizer");
+        for key in 0..150 {
+            assert_eq!(composed.get(key), second.get(first.get(key)), "{}", key);
+        }
+    }
+
+    #[test]
+    fn test_seed_to_location() {
+        let almanac = Almanac::from_str(EXAMPLE).unwrap();
+        let seed_to_location = &almanac.seed_to_location;
+        let chain = [
+            "seed-to-soil",
+            "soil-to-fertilizer",
+            "fertilizer-to-water",
+            "water-to-light",
+            "light-to-temperature",
+            "temperature-to-humidity",
+            "humidity-to-location",
+        ];
+
+        assert_eq!(seed_to_location.name(), "seed-to-location");
+        for key in 0..150 {
+            let expected = chain
+                .iter()
+                .fold(key, |key, name| almanac.maps[*name].get(key));
+            assert_eq!(seed_to_location.get(key), expected, "{}", key);
+        }
+    }
+
+    #[test]
+    fn test_invert() {
+        let almanac = Almanac::from_str(EXAMPLE).unwrap();
+        let map = &almanac.maps["fertilizer-to-water"];
+        let inverse = map.invert();
+
+        assert_eq!(inverse.name(), "water-to-fertilizer");
+        assert_eq!(inverse.get(49), 53);
+        for key in 0..150 {
+            assert_eq!(inverse.get(map.get(key)), key, "{}", key);
+        }
+
+        let location_to_seed = almanac.location_to_seed();
+        assert_eq!(location_to_seed.name(), "location-to-seed");
+        for seed in &almanac.seeds {
+            let location = almanac.seed_to_location.get(*seed);
+            assert_eq!(location_to_seed.get(location), *seed);
+        }
+    }
+
+    #[test]
+    fn test_invert_not_injective() {
+        let map = Map::new("a", "b", vec![Category::new(0, 10, 5)]);
+        let inverse = map.invert();
+
+        assert_eq!(inverse.get(12), 2);
+        assert_eq!(inverse.get(3), 3);
+    }
+
+    #[test]
+    fn test_map_display() {
+        let almanac = Almanac::from_str(EXAMPLE).unwrap();
+        let map = &almanac.maps["seed-to-soil"];
+        let printed = map.to_string();
+
+        assert_eq!(printed, "seed-to-soil map:\n52 50 48\n50 98 2\n");
+
+        let seed_to_location = &almanac.seed_to_location;
+        let reparsed =
+            Map::from_section(&sections(&seed_to_location.to_string()).next().unwrap()).unwrap();
+        assert_eq!(reparsed.name(), seed_to_location.name());
+        assert_eq!(reparsed.categories, seed_to_location.categories);
+    }
+
+    #[test]
+    fn test_example_part2() {
+        assert_eq!(
+            find_lowest_location_number_for_ranges(&Almanac::from_str(EXAMPLE).unwrap()),
+            46
+        );
+    }
+
+    #[test]
+    fn test_example_part2_by_search() {
+        assert_eq!(
+            find_lowest_location_number_by_search(&Almanac::from_str(EXAMPLE).unwrap()),
+            Some(46)
+        );
+    }
+
+    #[test]
+    fn test_result_part1() {
+        assert_eq!(Day05::part1(&Day05::parse(INPUT).unwrap()), 382895070);
+    }
+
+    #[test]
+    fn test_result_part2() {
+        assert_eq!(Day05::part2(&Day05::parse(INPUT).unwrap()), Some(17729182));
+        assert_eq!(
+            find_lowest_location_number_by_search(&Day05::parse(INPUT).unwrap()),
+            Some(17729182)
+        );
+    }
+}
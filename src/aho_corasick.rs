@@ -0,0 +1,243 @@
+//! Finding several patterns at once with an Aho–Corasick automaton.
+
+use std::collections::VecDeque;
+
+/// A match of pattern number `pattern` at the byte range `start..end` of the haystack.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Match {
+    pub pattern: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Finds all occurrences of a set of patterns in a single pass over the haystack, including
+/// occurrences that overlap, like `one` and `eight` in `oneight`.
+#[derive(Clone, Debug)]
+pub struct AhoCorasick {
+    states: Vec<State>,
+    lens: Vec<usize>,
+    /// The full transition table, 256 entries per state, so that every byte is one lookup.
+    delta: Vec<u32>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct State {
+    /// The trie edges out of this state.
+    next: Vec<(u8, usize)>,
+    /// The state for the longest proper suffix of this one that is also in the trie.
+    fail: usize,
+    /// The patterns that end here, longest first.
+    outputs: Vec<usize>,
+}
+
+impl AhoCorasick {
+    /// Builds the automaton. Patterns are numbered in order; empty patterns never match.
+    pub fn new<P: AsRef<[u8]>>(patterns: impl IntoIterator<Item = P>) -> Self {
+        let mut matcher = Self {
+            states: vec![State::default()],
+            lens: Vec::new(),
+            delta: Vec::new(),
+        };
+
+        for (id, pattern) in patterns.into_iter().enumerate() {
+            let pattern = pattern.as_ref();
+            matcher.lens.push(pattern.len());
+            if pattern.is_empty() {
+                continue;
+            }
+
+            let mut state = 0;
+            for &b in pattern {
+                state = match matcher.goto(state, b) {
+                    Some(next) => next,
+                    None => {
+                        matcher.states.push(State::default());
+                        let next = matcher.states.len() - 1;
+                        matcher.states[state].next.push((b, next));
+                        next
+                    }
+                };
+            }
+            matcher.states[state].outputs.push(id);
+        }
+
+        // Breadth-first, so that the fail state of every state is done before its children.
+        let mut queue = matcher.states[0]
+            .next
+            .iter()
+            .map(|(_, s)| *s)
+            .collect::<VecDeque<_>>();
+        while let Some(state) = queue.pop_front() {
+            for (b, child) in matcher.states[state].next.clone() {
+                let fail = matcher.step(matcher.states[state].fail, b);
+                let inherited = matcher.states[fail].outputs.clone();
+
+                matcher.states[child].fail = fail;
+                matcher.states[child].outputs.extend(inherited);
+                queue.push_back(child);
+            }
+        }
+
+        matcher.delta = (0..matcher.states.len())
+            .flat_map(|state| (0..=255).map(move |b| (state, b)))
+            .map(|(state, b)| matcher.step(state, b) as u32)
+            .collect();
+        matcher
+    }
+
+    /// All matches in `haystack`, ordered by where they end and then longest first.
+    pub fn find_overlapping<'a>(&'a self, haystack: &'a str) -> impl Iterator<Item = Match> + 'a {
+        let mut state = 0;
+        haystack.bytes().enumerate().flat_map(move |(i, b)| {
+            state = self.delta[state * 256 + b as usize] as usize;
+            self.states[state]
+                .outputs
+                .iter()
+                .map(move |&pattern| Match {
+                    pattern,
+                    start: i + 1 - self.lens[pattern],
+                    end: i + 1,
+                })
+        })
+    }
+
+    /// The longest match that starts right at the beginning of `haystack`, if any.
+    pub fn longest_prefix(&self, haystack: &str) -> Option<Match> {
+        let mut state = 0;
+        let mut longest = None;
+        for (i, b) in haystack.bytes().enumerate() {
+            match self.goto(state, b) {
+                Some(next) => state = next,
+                None => break,
+            }
+
+            // Only a pattern as long as the path from the root starts at the beginning.
+            if let Some(&pattern) = self.states[state].outputs.first() {
+                if self.lens[pattern] == i + 1 {
+                    longest = Some(Match {
+                        pattern,
+                        start: 0,
+                        end: i + 1,
+                    });
+                }
+            }
+        }
+
+        longest
+    }
+
+    fn goto(&self, state: usize, b: u8) -> Option<usize> {
+        self.states[state]
+            .next
+            .iter()
+            .find(|(edge, _)| *edge == b)
+            .map(|(_, next)| *next)
+    }
+
+    /// Follows `b` from `state`, falling back along the fail states until it can.
+    fn step(&self, mut state: usize, b: u8) -> usize {
+        loop {
+            if let Some(next) = self.goto(state, b) {
+                return next;
+            }
+            if state == 0 {
+                return 0;
+            }
+            state = self.states[state].fail;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    fn matches(patterns: &[&str], haystack: &str) -> Vec<(usize, usize, usize)> {
+        AhoCorasick::new(patterns)
+            .find_overlapping(haystack)
+            .map(|m| (m.pattern, m.start, m.end))
+            .collect()
+    }
+
+    #[test]
+    fn test_find_overlapping() {
+        assert_eq!(
+            matches(&["he", "she", "his", "hers"], "ushers"),
+            vec![(1, 1, 4), (0, 2, 4), (3, 2, 6)]
+        );
+        assert_eq!(
+            matches(&["one", "eight", "two"], "xoneightwo"),
+            vec![(0, 1, 4), (1, 3, 8), (2, 7, 10)]
+        );
+        assert_eq!(
+            matches(&["aa"], "aaaa"),
+            vec![(0, 0, 2), (0, 1, 3), (0, 2, 4)]
+        );
+        assert_eq!(matches(&["red"], "blue"), vec![]);
+    }
+
+    #[test]
+    fn test_find_overlapping_edge_cases() {
+        assert_eq!(matches(&["", "a"], "aa"), vec![(1, 0, 1), (1, 1, 2)]);
+        assert_eq!(matches(&["ab", "ab"], "ab"), vec![(0, 0, 2), (1, 0, 2)]);
+        assert_eq!(matches(&[], "ab"), vec![]);
+        assert_eq!(matches(&["é"], "café"), vec![(0, 3, 5)]);
+    }
+
+    #[test]
+    fn test_longest_prefix() {
+        let matcher = AhoCorasick::new(["red", "reddish", "green"]);
+
+        assert_eq!(
+            matcher.longest_prefix("reddish brown"),
+            Some(Match {
+                pattern: 1,
+                start: 0,
+                end: 7
+            })
+        );
+        assert_eq!(matcher.longest_prefix("reddi").map(|m| m.pattern), Some(0));
+        assert_eq!(matcher.longest_prefix("green").map(|m| m.end), Some(5));
+        assert_eq!(matcher.longest_prefix(" red"), None);
+        assert_eq!(matcher.longest_prefix("blue"), None);
+    }
+
+    fn word(rng: &mut Rng, max_len: usize) -> String {
+        (0..rng.below(max_len) + 1)
+            .map(|_| ['a', 'b', 'c'][rng.below(3)])
+            .collect()
+    }
+
+    #[test]
+    fn test_find_overlapping_matches_naive_search() {
+        let mut rng = Rng(0x2023_1201);
+
+        for _ in 0..2_000 {
+            let patterns = (0..rng.below(5) + 1)
+                .map(|_| word(&mut rng, 4))
+                .collect::<Vec<_>>();
+            let haystack = word(&mut rng, 30);
+
+            let mut expected = Vec::new();
+            for end in 1..=haystack.len() {
+                let mut ending_here = patterns
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, p)| haystack[..end].ends_with(p.as_str()))
+                    .map(|(i, p)| (i, end - p.len(), end))
+                    .collect::<Vec<_>>();
+                ending_here.sort_by_key(|(i, start, _)| (*start, *i));
+                expected.extend(ending_here);
+            }
+
+            let mut actual = AhoCorasick::new(&patterns)
+                .find_overlapping(&haystack)
+                .map(|m| (m.pattern, m.start, m.end))
+                .collect::<Vec<_>>();
+            // Matches of equal length ending at the same place can come in any order.
+            actual.sort_by_key(|(i, start, end)| (*end, *start, *i));
+            assert_eq!(actual, expected, "{:?} in {:?}", patterns, haystack);
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use std::{borrow::Cow, error::Error, fmt};
+
+/// An error produced while parsing puzzle input.
+///
+/// Carries enough context to point at the offending spot: the 1-based `line` and `column`,
+/// what the parser `expected`, what it `found` instead and the `snippet` (the whole line) it
+/// was looking at.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: Cow<'static, str>,
+    pub found: String,
+    pub snippet: String,
+}
+impl ParseError {
+    /// Creates an error at byte offset `offset` of `snippet`. The line number defaults to 1,
+    /// callers that know better should use [`ParseError::on_line`].
+    pub fn new(snippet: &str, offset: usize, expected: impl Into<Cow<'static, str>>) -> Self {
+        let found = snippet
+            .get(offset..)
+            .and_then(|rest| rest.split_whitespace().next())
+            .unwrap_or_default();
+
+        Self {
+            line: 1,
+            column: snippet.get(..offset).map_or(offset, |s| s.chars().count()) + 1,
+            expected: expected.into(),
+            found: if found.is_empty() {
+                "end of line".into()
+            } else {
+                format!("'{}'", found)
+            },
+            snippet: snippet.to_string(),
+        }
+    }
+
+    /// Creates an error pointing at `part`, which must be a subslice of `snippet` (as returned
+    /// by `split`, `trim` and friends).
+    pub fn at(snippet: &str, part: &str, expected: impl Into<Cow<'static, str>>) -> Self {
+        Self::new(snippet, offset_of(snippet, part), expected)
+    }
+
+    /// Creates an error pointing just past the end of `snippet`.
+    pub fn at_end(snippet: &str, expected: impl Into<Cow<'static, str>>) -> Self {
+        Self::new(snippet, snippet.len(), expected)
+    }
+
+    pub fn on_line(self, line: usize) -> Self {
+        Self { line, ..self }
+    }
+
+    /// Re-anchors an error produced for `part` onto the enclosing `snippet`, adjusting the
+    /// column accordingly.
+    pub fn within(self, snippet: &str, part: &str) -> Self {
+        let column = snippet[..offset_of(snippet, part)].chars().count() + self.column;
+
+        Self {
+            column,
+            snippet: snippet.to_string(),
+            ..self
+        }
+    }
+}
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "line {}, column {}: expected {}, found {}",
+            self.line, self.column, self.expected, self.found
+        )?;
+        writeln!(f, "    {}", self.snippet)?;
+        write!(f, "    {:>1$}", "^", self.column)
+    }
+}
+impl Error for ParseError {}
+
+/// The byte offset of `part` within `snippet`, which it must be a subslice of.
+pub(crate) fn offset_of(snippet: &str, part: &str) -> usize {
+    let offset = (part.as_ptr() as usize).wrapping_sub(snippet.as_ptr() as usize);
+    assert!(
+        offset <= snippet.len(),
+        "part must be a subslice of snippet"
+    );
+    offset
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let e = ParseError::new("Game 1; 3 blue", 6, "':'");
+
+        assert_eq!(e.line, 1);
+        assert_eq!(e.column, 7);
+        assert_eq!(e.expected, "':'");
+        assert_eq!(e.found, "';'");
+        assert_eq!(e.snippet, "Game 1; 3 blue");
+    }
+
+    #[test]
+    fn test_at_end() {
+        let e = ParseError::at_end("Game 1", "':'").on_line(3);
+
+        assert_eq!(e.line, 3);
+        assert_eq!(e.column, 7);
+        assert_eq!(e.found, "end of line");
+    }
+
+    #[test]
+    fn test_within() {
+        let line = "Game 1: 3 purple";
+        let (_, sets) = line.split_once(':').unwrap();
+        let e = ParseError::at(sets, sets.trim(), "a colour").within(line, sets);
+
+        assert_eq!(e.column, 9);
+        assert_eq!(e.found, "'3'");
+        assert_eq!(e.snippet, line);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            ParseError::new("Card 1 41 | 83", 6, "':'")
+                .on_line(2)
+                .to_string(),
+            "line 2, column 7: expected ':', found '41'\n    Card 1 41 | 83\n          ^"
+        );
+    }
+}
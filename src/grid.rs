@@ -0,0 +1,388 @@
+//! A dense 2D grid, as found in most grid puzzles.
+
+use std::{
+    collections::{HashSet, VecDeque},
+    fmt,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use crate::{numbered_lines, ParseError};
+
+/// A position in a [`Grid`]. `row` grows downwards and `col` grows to the right.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub row: usize,
+    pub col: usize,
+}
+impl Point {
+    pub fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+
+    /// Moves by `offset`, or returns `None` when that would leave the first quadrant.
+    pub fn offset(self, offset: Offset) -> Option<Point> {
+        Some(Point::new(
+            self.row.checked_add_signed(offset.row)?,
+            self.col.checked_add_signed(offset.col)?,
+        ))
+    }
+}
+
+/// A relative movement between two [`Point`]s.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Offset {
+    pub row: isize,
+    pub col: isize,
+}
+impl Offset {
+    pub const UP: Offset = Offset::new(-1, 0);
+    pub const DOWN: Offset = Offset::new(1, 0);
+    pub const LEFT: Offset = Offset::new(0, -1);
+    pub const RIGHT: Offset = Offset::new(0, 1);
+
+    /// The 4-neighbourhood, clockwise from up.
+    pub const ORTHOGONAL: [Offset; 4] = [Offset::UP, Offset::RIGHT, Offset::DOWN, Offset::LEFT];
+
+    /// The 8-neighbourhood, clockwise from up.
+    pub const ALL: [Offset; 8] = [
+        Offset::UP,
+        Offset::new(-1, 1),
+        Offset::RIGHT,
+        Offset::new(1, 1),
+        Offset::DOWN,
+        Offset::new(1, -1),
+        Offset::LEFT,
+        Offset::new(-1, -1),
+    ];
+
+    pub const fn new(row: isize, col: isize) -> Self {
+        Self { row, col }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+impl<T> Grid<T> {
+    /// Creates a grid from its cells in row-major order.
+    ///
+    /// # Panics
+    ///
+    /// If the number of cells isn't `width * height`.
+    pub fn from_cells(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "Cell count");
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses one cell per character with `f`. Lines are trimmed and blank lines skipped (see
+    /// [`numbered_lines`]), and every line must be equally long.
+    pub fn parse_with(s: &str, mut f: impl FnMut(char) -> Option<T>) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for (n, line) in numbered_lines(s) {
+            for (i, c) in line.char_indices() {
+                let cell =
+                    f(c).ok_or_else(|| ParseError::new(line, i, "a valid cell").on_line(n))?;
+                cells.push(cell);
+            }
+
+            let len = line.chars().count();
+            match width {
+                None => width = Some(len),
+                Some(width) if width != len => {
+                    let expected = format!("a line of width {}", width);
+                    return Err(ParseError::new(line, 0, expected).on_line(n));
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+
+        Ok(Self::from_cells(width.unwrap_or(0), height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        p.row < self.height && p.col < self.width
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.contains(p).then(|| &self.cells[self.index(p)])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        if self.contains(p) {
+            let i = self.index(p);
+            Some(&mut self.cells[i])
+        } else {
+            None
+        }
+    }
+
+    /// All points of the grid in row-major order.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| Point::new(row, col)))
+    }
+
+    /// All cells of the grid along with their positions, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        (row < self.height).then(|| &self.cells[row * self.width..(row + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        let cells = if col < self.width {
+            &self.cells[col..]
+        } else {
+            &[]
+        };
+        cells.iter().step_by(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    /// The in-bounds points of the 4-neighbourhood of `p`.
+    pub fn neighbours4(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(p, &Offset::ORTHOGONAL)
+    }
+
+    /// The in-bounds points of the 8-neighbourhood of `p`, including diagonals.
+    pub fn neighbours8(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(p, &Offset::ALL)
+    }
+
+    fn neighbours<'a>(
+        &'a self,
+        p: Point,
+        offsets: &'static [Offset],
+    ) -> impl Iterator<Item = Point> + 'a {
+        offsets
+            .iter()
+            .filter_map(move |o| p.offset(*o))
+            .filter(|p| self.contains(*p))
+    }
+
+    /// The connected region (through the 4-neighbourhood) of cells around `start` that match
+    /// `f`, in the order they were found. Empty if `start` itself doesn't match.
+    pub fn region(&self, start: Point, f: impl Fn(&T) -> bool) -> Vec<Point> {
+        let mut region = Vec::new();
+        let mut seen = HashSet::new();
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(p) = queue.pop_front() {
+            if !self.get(p).is_some_and(&f) || !seen.insert(p) {
+                continue;
+            }
+
+            region.push(p);
+            queue.extend(self.neighbours4(p));
+        }
+
+        region
+    }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Grid<U> {
+        Grid::from_cells(
+            self.width,
+            self.height,
+            self.cells.into_iter().map(f).collect(),
+        )
+    }
+
+    #[inline]
+    fn index(&self, p: Point) -> usize {
+        p.row * self.width + p.col
+    }
+}
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self::from_cells(width, height, vec![value; width * height])
+    }
+
+    /// Copies out the `width` x `height` rectangle with its top left corner at `top_left`, or
+    /// returns `None` if it doesn't fit in the grid.
+    pub fn subgrid(&self, top_left: Point, width: usize, height: usize) -> Option<Grid<T>> {
+        if top_left.row + height > self.height || top_left.col + width > self.width {
+            return None;
+        }
+
+        let cells = (top_left.row..top_left.row + height)
+            .flat_map(|row| {
+                let start = self.index(Point::new(row, top_left.col));
+                self.cells[start..start + width].iter().cloned()
+            })
+            .collect();
+
+        Some(Grid::from_cells(width, height, cells))
+    }
+}
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &Self::Output {
+        self.get(p).expect("Point out of bounds")
+    }
+}
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut Self::Output {
+        self.get_mut(p).expect("Point out of bounds")
+    }
+}
+impl FromStr for Grid<char> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, Some)
+    }
+}
+impl fmt::Display for Grid<char> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            writeln!(f, "{}", row.iter().collect::<String>())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r#"
+    467..114..
+    ...*......
+    ..35..633.
+    "#;
+
+    fn example() -> Grid<char> {
+        EXAMPLE.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = example();
+
+        assert_eq!((grid.width(), grid.height()), (10, 3));
+        assert_eq!(grid[Point::new(1, 3)], '*');
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(0, 10)), None);
+        assert_eq!(grid.to_string(), "467..114..\n...*......\n..35..633.\n");
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        let e = "..\n...".parse::<Grid<char>>().unwrap_err();
+        assert_eq!((e.line, e.expected.as_ref()), (2, "a line of width 2"));
+
+        let e = Grid::parse_with("01\n21", |c| c.to_digit(2)).unwrap_err();
+        assert_eq!((e.line, e.column), (2, 1));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = example();
+
+        assert_eq!(
+            grid.neighbours4(Point::new(0, 0)).collect::<Vec<_>>(),
+            vec![Point::new(0, 1), Point::new(1, 0)]
+        );
+        assert_eq!(grid.neighbours4(Point::new(1, 1)).count(), 4);
+        assert_eq!(grid.neighbours8(Point::new(0, 0)).count(), 3);
+        assert_eq!(grid.neighbours8(Point::new(1, 1)).count(), 8);
+        assert_eq!(grid.neighbours8(Point::new(2, 9)).count(), 3);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = Grid::from_cells(3, 2, vec![1, 2, 3, 4, 5, 6]);
+
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![&[1, 2, 3], &[4, 5, 6]]
+        );
+        assert_eq!(grid.row(1), Some(&[4, 5, 6][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.column(1).collect::<Vec<_>>(), vec![&2, &5]);
+        assert_eq!(grid.column(3).count(), 0);
+        assert_eq!(
+            grid.columns().map(|c| c.sum::<i32>()).collect::<Vec<_>>(),
+            vec![5, 7, 9]
+        );
+    }
+
+    #[test]
+    fn test_region() {
+        let grid = example();
+        let mut region = grid.region(Point::new(0, 3), |c| *c == '.');
+        region.sort();
+
+        assert_eq!(
+            region,
+            vec![
+                Point::new(0, 3),
+                Point::new(0, 4),
+                Point::new(0, 8),
+                Point::new(0, 9),
+                Point::new(1, 4),
+                Point::new(1, 5),
+                Point::new(1, 6),
+                Point::new(1, 7),
+                Point::new(1, 8),
+                Point::new(1, 9),
+                Point::new(2, 4),
+                Point::new(2, 5),
+                Point::new(2, 9),
+            ]
+        );
+        assert!(grid.region(Point::new(0, 0), |c| *c == '.').is_empty());
+    }
+
+    #[test]
+    fn test_subgrid() {
+        let grid = example();
+
+        assert_eq!(
+            grid.subgrid(Point::new(1, 2), 3, 2).unwrap().to_string(),
+            ".*.\n35.\n"
+        );
+        assert!(grid.subgrid(Point::new(1, 8), 3, 2).is_none());
+    }
+
+    #[test]
+    fn test_index_mut() {
+        let mut grid = Grid::new(2, 2, 0);
+        grid[Point::new(1, 0)] = 5;
+
+        assert_eq!(
+            grid.map(|n| n * 2).rows().collect::<Vec<_>>(),
+            vec![&[0, 0], &[10, 0]]
+        );
+    }
+}
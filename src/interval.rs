@@ -0,0 +1,346 @@
+//! Half-open integer intervals and sets of them.
+
+use std::{
+    fmt,
+    ops::{Add, Range, Sub},
+};
+
+/// The half-open interval `start..end`. Intervals with `end <= start` are empty.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+impl<T> Interval<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    /// Creates `start..end`. An `end` before `start` is clamped, giving an empty interval.
+    pub fn new(start: T, end: T) -> Self {
+        Self {
+            start,
+            end: end.max(start),
+        }
+    }
+
+    pub fn from_len(start: T, len: T) -> Self {
+        Self::new(start, start + len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    pub fn len(&self) -> T {
+        self.end - self.start
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.intersect(other).is_some()
+    }
+
+    /// The values in both `self` and `other`, or `None` if there are none.
+    pub fn intersect(&self, other: &Self) -> Option<Self> {
+        let intersection = Self::new(self.start.max(other.start), self.end.min(other.end));
+        (!intersection.is_empty()).then_some(intersection)
+    }
+
+    /// The values in `self` but not in `other`: the (non-empty) parts before and after it.
+    pub fn subtract(&self, other: &Self) -> IntervalSet<T> {
+        if !self.overlaps(other) {
+            return IntervalSet::from_iter([*self]);
+        }
+
+        IntervalSet::from_iter([
+            Self::new(self.start, other.start.min(self.end)),
+            Self::new(other.end.max(self.start), self.end),
+        ])
+    }
+
+    pub fn union(&self, other: &Self) -> IntervalSet<T> {
+        IntervalSet::from_iter([*self, *other])
+    }
+
+    /// Splits into the (non-empty) parts before `at` and from `at` onwards.
+    pub fn split_at(&self, at: T) -> (Option<Self>, Option<Self>) {
+        let at = at.clamp(self.start, self.end);
+        let before = Self::new(self.start, at);
+        let after = Self::new(at, self.end);
+
+        (
+            (!before.is_empty()).then_some(before),
+            (!after.is_empty()).then_some(after),
+        )
+    }
+
+    /// Moves the interval so that `from` ends up at `to`, keeping its length. `from` must not be
+    /// after `start`.
+    pub fn shift(&self, from: T, to: T) -> Self {
+        Self::from_len(to + (self.start - from), self.len())
+    }
+}
+impl<T> From<Range<T>> for Interval<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    fn from(range: Range<T>) -> Self {
+        Self::new(range.start, range.end)
+    }
+}
+impl<T: fmt::Debug> fmt::Debug for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}..{:?}", self.start, self.end)
+    }
+}
+
+/// A set of values stored as sorted, non-overlapping and non-adjacent [`Interval`]s.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+impl<T> IntervalSet<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    pub fn new() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The intervals making up the set, in order.
+    pub fn iter(&self) -> impl Iterator<Item = &Interval<T>> {
+        self.intervals.iter()
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|i| i.start)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.intervals.partition_point(|i| i.end <= value);
+        self.intervals.get(i).is_some_and(|i| i.contains(value))
+    }
+
+    /// Adds every value in `interval` to the set, merging it with the intervals it overlaps or
+    /// touches.
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+
+        let first = self.intervals.partition_point(|i| i.end < interval.start);
+        let last = self.intervals.partition_point(|i| i.start <= interval.end);
+        let merged = self.intervals[first..last].iter().fold(interval, |acc, i| {
+            Interval::new(acc.start.min(i.start), acc.end.max(i.end))
+        });
+
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.iter().chain(other.iter()).copied().collect()
+    }
+
+    pub fn intersect(&self, other: &Self) -> Self {
+        self.iter()
+            .flat_map(|a| other.iter().filter_map(|b| a.intersect(b)))
+            .collect()
+    }
+
+    pub fn subtract(&self, other: &Self) -> Self {
+        other.iter().fold(self.clone(), |set, b| {
+            set.iter().flat_map(|a| a.subtract(b).intervals).collect()
+        })
+    }
+
+    /// Splits into the values before `at` and the values from `at` onwards.
+    pub fn split_at(&self, at: T) -> (Self, Self) {
+        let (before, after): (Vec<_>, Vec<_>) = self.iter().map(|i| i.split_at(at)).unzip();
+
+        (
+            before.into_iter().flatten().collect(),
+            after.into_iter().flatten().collect(),
+        )
+    }
+}
+impl<T> Default for IntervalSet<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T> FromIterator<Interval<T>> for IntervalSet<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+impl<T> Extend<Interval<T>> for IntervalSet<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    fn extend<I: IntoIterator<Item = Interval<T>>>(&mut self, iter: I) {
+        for interval in iter {
+            self.insert(interval);
+        }
+    }
+}
+impl<T: fmt::Debug> fmt::Debug for IntervalSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(&self.intervals).finish()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::single_range_in_vec_init)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    fn set(intervals: &[Range<u32>]) -> IntervalSet<u32> {
+        intervals.iter().cloned().map(Interval::from).collect()
+    }
+
+    #[test]
+    fn test_interval() {
+        let i = Interval::new(10, 15);
+
+        assert_eq!(i.len(), 5);
+        assert!(i.contains(10) && i.contains(14));
+        assert!(!i.contains(9) && !i.contains(15));
+        assert!(Interval::new(15, 10).is_empty());
+        assert_eq!(Interval::from_len(10, 5), i);
+    }
+
+    #[test]
+    fn test_interval_intersect() {
+        let i = Interval::new(10, 15);
+
+        assert_eq!(
+            i.intersect(&Interval::new(12, 20)),
+            Some(Interval::new(12, 15))
+        );
+        assert_eq!(i.intersect(&Interval::new(0, 20)), Some(i));
+        assert_eq!(i.intersect(&Interval::new(15, 20)), None);
+        assert_eq!(i.intersect(&Interval::new(0, 10)), None);
+        assert!(!i.overlaps(&Interval::new(15, 16)));
+    }
+
+    #[test]
+    fn test_interval_subtract() {
+        let i = Interval::new(10, 15);
+
+        assert_eq!(i.subtract(&Interval::new(12, 13)), set(&[10..12, 13..15]));
+        assert_eq!(i.subtract(&Interval::new(0, 12)), set(&[12..15]));
+        assert_eq!(i.subtract(&Interval::new(0, 20)), set(&[]));
+        assert_eq!(i.subtract(&Interval::new(20, 30)), set(&[10..15]));
+    }
+
+    #[test]
+    fn test_interval_union() {
+        let i = Interval::new(10, 15);
+
+        assert_eq!(i.union(&Interval::new(15, 20)), set(&[10..20]));
+        assert_eq!(i.union(&Interval::new(16, 20)), set(&[10..15, 16..20]));
+    }
+
+    #[test]
+    fn test_interval_split_at() {
+        let i = Interval::new(10, 15);
+
+        assert_eq!(
+            i.split_at(12),
+            (Some(Interval::new(10, 12)), Some(Interval::new(12, 15)))
+        );
+        assert_eq!(i.split_at(10), (None, Some(i)));
+        assert_eq!(i.split_at(0), (None, Some(i)));
+        assert_eq!(i.split_at(20), (Some(i), None));
+    }
+
+    #[test]
+    fn test_interval_shift() {
+        assert_eq!(
+            Interval::new(12, 15).shift(10, 100),
+            Interval::new(102, 105)
+        );
+        assert_eq!(Interval::new(12, 15).shift(12, 0), Interval::new(0, 3));
+    }
+
+    #[test]
+    fn test_interval_set_insert() {
+        assert_eq!(set(&[0..2, 4..6, 1..5]), set(&[0..6]));
+        assert_eq!(set(&[4..6, 0..2, 2..3]), set(&[0..3, 4..6]));
+        assert_eq!(set(&[0..2, 3..3]), set(&[0..2]));
+        assert!(set(&[0..2, 4..6]).contains(5));
+        assert!(!set(&[0..2, 4..6]).contains(2));
+    }
+
+    fn random_set(rng: &mut Rng) -> (IntervalSet<u32>, u64) {
+        let intervals = (0..rng.below(4))
+            .map(|_| {
+                let start = rng.below(64) as u32;
+                Interval::new(start, start + rng.below(16) as u32)
+            })
+            .collect::<Vec<_>>();
+
+        (intervals.iter().copied().collect(), bits(intervals))
+    }
+
+    /// The reference model: the values in `intervals` (all below 64) as a bit set.
+    fn bits(intervals: impl IntoIterator<Item = Interval<u32>>) -> u64 {
+        intervals
+            .into_iter()
+            .flat_map(|i| i.start.min(64)..i.end.min(64))
+            .fold(0, |bits, n| bits | 1 << n)
+    }
+
+    fn is_normalized(set: &IntervalSet<u32>) -> bool {
+        set.iter().all(|i| !i.is_empty()) && set.intervals.windows(2).all(|w| w[0].end < w[1].start)
+    }
+
+    #[test]
+    fn test_interval_set_properties() {
+        let mut rng = Rng(0x2023_1205);
+
+        for _ in 0..10_000 {
+            let (a, a_bits) = random_set(&mut rng);
+            let (b, b_bits) = random_set(&mut rng);
+            let at = rng.below(80) as u32;
+
+            let union = a.union(&b);
+            let intersection = a.intersect(&b);
+            let difference = a.subtract(&b);
+            let (before, after) = a.split_at(at);
+
+            for set in [&a, &union, &intersection, &difference, &before, &after] {
+                assert!(is_normalized(set), "{:?}", set);
+            }
+            assert_eq!(bits(union.iter().copied()), a_bits | b_bits);
+            assert_eq!(bits(intersection.iter().copied()), a_bits & b_bits);
+            assert_eq!(bits(difference.iter().copied()), a_bits & !b_bits);
+            assert_eq!(
+                bits(before.iter().copied()) | bits(after.iter().copied()),
+                a_bits
+            );
+            assert!(before.iter().all(|i| i.end <= at) && after.iter().all(|i| i.start >= at));
+            for n in 0..64 {
+                assert_eq!(a.contains(n), a_bits & 1 << n != 0);
+            }
+        }
+    }
+}
@@ -1,16 +1,258 @@
-pub fn extract_number(s: &str) -> u32 {
-    s.chars()
-        .flat_map(|c| c.to_digit(10).into_iter())
-        .rev()
-        .enumerate()
-        .map(|(i, n)| 10u32.pow(i as u32) * n)
-        .sum()
-}
+use std::{
+    borrow::Cow,
+    fmt::Display,
+    fs,
+    io::{self, Read},
+    iter,
+    str::FromStr,
+};
+
+pub mod aho_corasick;
+mod error;
+pub mod grid;
+pub mod interval;
+mod number;
+pub mod parser;
+#[cfg(test)]
+mod rng;
+
+pub use error::ParseError;
+pub use number::{
+    checked_extract_number, checked_extract_numbers, extract_number, extract_numbers, Integer,
+};
 
 pub fn normalized_lines(s: &str) -> impl Iterator<Item = &str> {
     s.lines().map(|s| s.trim()).filter(|s| !s.is_empty())
 }
 
+/// Like [`normalized_lines`], but also yields the 1-based line number of each line in `s`, for
+/// error reporting.
+pub fn numbered_lines(s: &str) -> impl Iterator<Item = (usize, &str)> {
+    s.lines()
+        .enumerate()
+        .map(|(i, s)| (i + 1, s.trim()))
+        .filter(|(_, s)| !s.is_empty())
+}
+
+/// A block of consecutive non-blank lines, see [`sections`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Section<'a> {
+    /// The 1-based line number of the first line.
+    pub start: usize,
+    pub lines: Vec<&'a str>,
+}
+impl<'a> Section<'a> {
+    /// The lines of this section along with their line numbers, like [`numbered_lines`].
+    pub fn numbered_lines(&self) -> impl Iterator<Item = (usize, &'a str)> + '_ {
+        self.lines
+            .iter()
+            .enumerate()
+            .map(|(i, l)| (self.start + i, *l))
+    }
+}
+
+/// Splits `s` into sections separated by one or more blank lines, the same blocks that
+/// [`parser::sections`] parses. Lines are trimmed the same way [`normalized_lines`] does.
+pub fn sections(s: &str) -> impl Iterator<Item = Section<'_>> {
+    let mut rest = s;
+    iter::from_fn(move || {
+        let (block, next) = parser::split_block(rest);
+        if block.is_empty() {
+            return None;
+        }
+        rest = next;
+
+        Some(Section {
+            start: s[..error::offset_of(s, block)].matches('\n').count() + 1,
+            lines: block.lines().map(str::trim).collect(),
+        })
+    })
+}
+
+/// How parsers treat lines that fail to parse.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Mode {
+    /// Fail on the first malformed line.
+    #[default]
+    Strict,
+    /// Skip malformed lines, but keep track of them.
+    Lenient,
+}
+
+/// A parsed value along with the errors for the lines that were skipped to produce it. Only
+/// ever non-empty in [`Mode::Lenient`].
+#[derive(Debug, PartialEq)]
+pub struct Parsed<T> {
+    pub value: T,
+    pub skipped: Vec<ParseError>,
+}
+impl<T> Parsed<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            skipped: Vec::new(),
+        }
+    }
+}
+
+/// Parses every non-blank line of `s` (see [`numbered_lines`]) as a `T`.
+pub fn parse_lines<T>(s: &str, mode: Mode) -> Result<Parsed<Vec<T>>, ParseError>
+where
+    T: FromStr<Err = ParseError>,
+{
+    let mut parsed = Parsed::new(Vec::new());
+    for (n, line) in numbered_lines(s) {
+        match T::from_str(line) {
+            Ok(item) => parsed.value.push(item),
+            Err(e) if mode == Mode::Lenient => parsed.skipped.push(e.on_line(n)),
+            Err(e) => return Err(e.on_line(n)),
+        }
+    }
+    Ok(parsed)
+}
+
+/// Loads the puzzle input at runtime.
+///
+/// `path` is read from disk, or from stdin when it is `-`. Without a path the `bundled`
+/// input (usually the day's `input.txt`) is used.
+pub fn read_input(path: Option<&str>, bundled: &'static str) -> io::Result<Cow<'static, str>> {
+    match path {
+        None => Ok(Cow::Borrowed(bundled)),
+        Some("-") => {
+            let mut s = String::new();
+            io::stdin().read_to_string(&mut s)?;
+            Ok(Cow::Owned(s))
+        }
+        Some(path) => fs::read_to_string(path).map(Cow::Owned),
+    }
+}
+
+/// A solution to a single day's puzzle.
+///
+/// The raw puzzle input is parsed once into [`Solution::Input`] and then handed to each part,
+/// so that tooling can drive any day the same way without scraping stdout.
+pub trait Solution {
+    type Input;
+    type Output: Display;
+
+    fn parse(s: &str) -> Result<Self::Input, ParseError>;
+
+    /// Parses `s` in the given [`Mode`]. Days whose input isn't made up of independent lines
+    /// have nothing to skip, so by default this is just [`Solution::parse`].
+    fn parse_with(s: &str, _mode: Mode) -> Result<Parsed<Self::Input>, ParseError> {
+        Self::parse(s).map(Parsed::new)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Output;
+
+    /// Not every day has a second part solved (yet), hence the default.
+    fn part2(_input: &Self::Input) -> Option<Self::Output> {
+        None
+    }
+}
+
 pub mod prelude {
-    pub use super::extract_number;
+    pub use super::{extract_number, read_input, Mode, ParseError, Solution};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbered_lines() {
+        assert_eq!(
+            numbered_lines("\n  a\n\n  b  \n").collect::<Vec<_>>(),
+            vec![(2, "a"), (4, "b")]
+        );
+    }
+
+    #[test]
+    fn test_sections() {
+        let s = r#"
+        seeds: 1 2
+
+        a-to-b map:
+        1 2 3
+        4 5 6
+
+
+        b-to-c map:
+        7 8 9
+        "#;
+
+        assert_eq!(
+            sections(s).collect::<Vec<_>>(),
+            vec![
+                Section {
+                    start: 2,
+                    lines: vec!["seeds: 1 2"]
+                },
+                Section {
+                    start: 4,
+                    lines: vec!["a-to-b map:", "1 2 3", "4 5 6"]
+                },
+                Section {
+                    start: 9,
+                    lines: vec!["b-to-c map:", "7 8 9"]
+                },
+            ]
+        );
+        assert_eq!(
+            sections(s).nth(1).unwrap().numbered_lines().last(),
+            Some((6, "4 5 6"))
+        );
+        assert_eq!(sections("\n  \n").count(), 0);
+        assert_eq!(sections("\r\na\r\n  \r\nb").count(), 2);
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Number(u32);
+    impl FromStr for Number {
+        type Err = ParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            s.parse()
+                .map(Number)
+                .map_err(|_| ParseError::new(s, 0, "a number"))
+        }
+    }
+
+    #[test]
+    fn test_parse_lines_strict() {
+        assert_eq!(
+            parse_lines::<Number>("1\n\n2", Mode::Strict),
+            Ok(Parsed::new(vec![Number(1), Number(2)]))
+        );
+
+        let e = parse_lines::<Number>("1\nx\n3\ny", Mode::Strict).unwrap_err();
+        assert_eq!((e.line, e.found.as_str()), (2, "'x'"));
+    }
+
+    #[test]
+    fn test_parse_lines_lenient() {
+        let parsed = parse_lines::<Number>("1\nx\n3\ny", Mode::Lenient).unwrap();
+
+        assert_eq!(parsed.value, vec![Number(1), Number(3)]);
+        assert_eq!(
+            parsed.skipped.iter().map(|e| e.line).collect::<Vec<_>>(),
+            vec![2, 4]
+        );
+    }
+
+    #[test]
+    fn test_read_input_bundled() {
+        assert_eq!(read_input(None, "bundled").unwrap(), "bundled");
+    }
+
+    #[test]
+    fn test_read_input_path() {
+        let path = std::env::temp_dir().join("aoc2023_test_read_input_path.txt");
+        fs::write(&path, "from file").unwrap();
+
+        assert_eq!(read_input(path.to_str(), "bundled").unwrap(), "from file");
+        assert!(read_input(Some("does/not/exist.txt"), "bundled").is_err());
+
+        fs::remove_file(path).unwrap();
+    }
 }
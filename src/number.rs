@@ -0,0 +1,198 @@
+use std::iter;
+
+use crate::{error::offset_of, ParseError};
+
+/// An integer type that numbers can be extracted into.
+pub trait Integer: Copy + Default {
+    const NAME: &'static str;
+    const SIGNED: bool;
+
+    /// Appends `digit` to `self`, i.e. `self * 10 + digit`, or `self * 10 - digit` for
+    /// negative numbers, returning `None` on overflow.
+    fn checked_push(self, digit: u8, negative: bool) -> Option<Self>;
+
+    /// Like [`Integer::checked_push`], but wraps around on overflow.
+    fn wrapping_push(self, digit: u8, negative: bool) -> Self;
+}
+
+macro_rules! impl_integer {
+    ($signed:expr => $($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const NAME: &'static str = stringify!($t);
+                const SIGNED: bool = $signed;
+
+                fn checked_push(self, digit: u8, negative: bool) -> Option<Self> {
+                    let n = self.checked_mul(10)?;
+                    if negative {
+                        n.checked_sub(digit as $t)
+                    } else {
+                        n.checked_add(digit as $t)
+                    }
+                }
+
+                fn wrapping_push(self, digit: u8, negative: bool) -> Self {
+                    let n = self.wrapping_mul(10);
+                    if negative {
+                        n.wrapping_sub(digit as $t)
+                    } else {
+                        n.wrapping_add(digit as $t)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(false => u8, u16, u32, u64, u128, usize);
+impl_integer!(true => i8, i16, i32, i64, i128, isize);
+
+/// Extracts the first integer in `s`, e.g. `12` from `"Game 12: ..."`. A `-` directly in front
+/// of the digits makes the number negative for signed types. Wraps around on overflow and
+/// returns `0` if there is no number at all, see [`checked_extract_number`] for a stricter
+/// version.
+pub fn extract_number<T: Integer>(s: &str) -> T {
+    extract_numbers(s).next().unwrap_or_default()
+}
+
+/// Like [`extract_number`], but fails if `s` doesn't contain a number or if the number doesn't
+/// fit in `T`.
+pub fn checked_extract_number<T: Integer>(s: &str) -> Result<T, ParseError> {
+    checked_extract_numbers(s)
+        .next()
+        .unwrap_or_else(|| Err(ParseError::new(s, 0, "a number")))
+}
+
+/// Extracts every integer in `s`, e.g. `[-1, 2]` from `"x=-1, y=2"`. Numbers are parsed like
+/// [`extract_number`] does.
+pub fn extract_numbers<T: Integer>(s: &str) -> impl Iterator<Item = T> + '_ {
+    find_numbers(s).map(|(negative, digits)| {
+        digits.bytes().fold(T::default(), |n, d| {
+            n.wrapping_push(d - b'0', negative && T::SIGNED)
+        })
+    })
+}
+
+/// Like [`extract_numbers`], but yields an error for every number that doesn't fit in `T`.
+pub fn checked_extract_numbers<T: Integer>(
+    s: &str,
+) -> impl Iterator<Item = Result<T, ParseError>> + '_ {
+    find_numbers(s).map(|(negative, digits)| {
+        let expected = || format!("a number that fits in {}", T::NAME);
+
+        if negative && !T::SIGNED {
+            return Err(ParseError::new(s, offset_of(s, digits) - 1, expected()));
+        }
+
+        digits
+            .bytes()
+            .try_fold(T::default(), |n, d| n.checked_push(d - b'0', negative))
+            .ok_or_else(|| ParseError::at(s, digits, expected()))
+    })
+}
+
+fn find_numbers(s: &str) -> impl Iterator<Item = (bool, &str)> {
+    let mut rest = s;
+    iter::from_fn(move || {
+        let (negative, digits) = find_number(rest)?;
+        rest = &rest[offset_of(rest, digits) + digits.len()..];
+        Some((negative, digits))
+    })
+}
+
+/// Finds the first run of ASCII digits in `s`, and whether it is preceded by a `-`.
+fn find_number(s: &str) -> Option<(bool, &str)> {
+    let start = s.find(|c: char| c.is_ascii_digit())?;
+    let len = s[start..]
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(s.len() - start);
+    let negative = s[..start].ends_with('-');
+
+    Some((negative, &s[start..start + len]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_number() {
+        assert_eq!(extract_number::<u32>("Game 12: 3 blue"), 12);
+        assert_eq!(extract_number::<u32>("a1b2"), 1);
+        assert_eq!(extract_number::<u32>("no digits"), 0);
+        assert_eq!(extract_number::<u64>("4294967296"), 4294967296);
+        assert_eq!(
+            extract_number::<u128>("340282366920938463463374607431768211455"),
+            u128::MAX
+        );
+    }
+
+    #[test]
+    fn test_extract_number_signed() {
+        assert_eq!(extract_number::<i64>("x=-42"), -42);
+        assert_eq!(extract_number::<i64>("- 42"), 42);
+        assert_eq!(extract_number::<i8>("-128"), i8::MIN);
+        assert_eq!(extract_number::<u32>("-42"), 42);
+    }
+
+    #[test]
+    fn test_extract_number_wraps() {
+        assert_eq!(extract_number::<u8>("256"), 0);
+        assert_eq!(extract_number::<u32>("4294967296"), 0);
+    }
+
+    #[test]
+    fn test_checked_extract_number() {
+        assert_eq!(checked_extract_number::<u32>("Card 7:"), Ok(7));
+        assert_eq!(checked_extract_number::<i32>("-7"), Ok(-7));
+        assert_eq!(checked_extract_number::<u8>("255"), Ok(255));
+        assert_eq!(checked_extract_number::<i8>("-128"), Ok(-128));
+    }
+
+    #[test]
+    fn test_extract_numbers() {
+        assert_eq!(
+            extract_numbers::<u32>("Card 1: 41 48 | 83  6").collect::<Vec<_>>(),
+            vec![1, 41, 48, 83, 6]
+        );
+        assert_eq!(
+            extract_numbers::<i64>("x=-1, y=2, z=-3-4").collect::<Vec<_>>(),
+            vec![-1, 2, -3, -4]
+        );
+        assert_eq!(
+            extract_numbers::<u64>("seed-to-soil").collect::<Vec<_>>(),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_checked_extract_numbers() {
+        assert_eq!(
+            checked_extract_numbers::<u8>("1 2 3").collect::<Result<Vec<_>, _>>(),
+            Ok(vec![1, 2, 3])
+        );
+
+        let e = checked_extract_numbers::<u8>("1 256 3 999")
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_err();
+        assert_eq!((e.column, e.found.as_str()), (3, "'256'"));
+    }
+
+    #[test]
+    fn test_checked_extract_number_invalid() {
+        let e = checked_extract_number::<u8>("x 256").unwrap_err();
+        assert_eq!(
+            (e.column, e.expected.as_ref(), e.found.as_str()),
+            (3, "a number that fits in u8", "'256'")
+        );
+
+        let e = checked_extract_number::<u32>("x -1").unwrap_err();
+        assert_eq!((e.column, e.found.as_str()), (3, "'-1'"));
+
+        let e = checked_extract_number::<i8>("128").unwrap_err();
+        assert_eq!(e.expected, "a number that fits in i8");
+
+        let e = checked_extract_number::<u32>("none").unwrap_err();
+        assert_eq!(e.expected, "a number");
+    }
+}
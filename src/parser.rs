@@ -0,0 +1,459 @@
+//! A small parser-combinator toolkit.
+//!
+//! A parser is any `Fn(&str) -> PResult<T>` that consumes a prefix of its input and returns the
+//! rest of it along with the parsed value. Token parsers ([`literal`], [`one_of`], [`keyword`],
+//! [`integer`] and [`identifier`]) skip leading spaces and tabs, but never newlines, so line structure is
+//! left to [`lines`] and [`sections`]. Use [`parse`] to run a parser on a whole input and turn
+//! a failure into a [`ParseError`] with the right line and column.
+
+use std::{borrow::Cow, sync::OnceLock};
+
+use crate::{aho_corasick::AhoCorasick, error::offset_of, Integer, ParseError};
+
+/// Where and why a parser failed. `rest` is the input remaining at the point of failure.
+#[derive(Debug, PartialEq)]
+pub struct Failure<'a> {
+    pub rest: &'a str,
+    pub expected: Cow<'static, str>,
+}
+impl<'a> Failure<'a> {
+    pub fn new(rest: &'a str, expected: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            rest,
+            expected: expected.into(),
+        }
+    }
+}
+
+pub type PResult<'a, T> = Result<(&'a str, T), Failure<'a>>;
+
+pub trait Parser<'a, T>: Fn(&'a str) -> PResult<'a, T> {}
+impl<'a, T, F: Fn(&'a str) -> PResult<'a, T>> Parser<'a, T> for F {}
+
+/// Runs `parser` on `input`, which must be consumed completely (save for trailing whitespace).
+pub fn parse<'a, T>(input: &'a str, parser: impl Parser<'a, T>) -> Result<T, ParseError> {
+    parser(input)
+        .and_then(|(rest, value)| end(rest).map(|_| value))
+        .map_err(|f| to_parse_error(input, f))
+}
+
+/// Converts `failure` into a [`ParseError`] with the line number, column and snippet it has
+/// within `input`. `failure.rest` must be a subslice of `input`.
+pub fn to_parse_error(input: &str, failure: Failure) -> ParseError {
+    let offset = offset_of(input, failure.rest);
+    let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = input[offset..]
+        .find('\n')
+        .map_or(input.len(), |i| offset + i);
+    let line = input[..offset].matches('\n').count() + 1;
+
+    ParseError::new(
+        input[line_start..line_end].trim_end_matches('\r'),
+        offset - line_start,
+        failure.expected,
+    )
+    .on_line(line)
+}
+
+fn skip_space(s: &str) -> &str {
+    s.trim_start_matches([' ', '\t'])
+}
+
+/// Succeeds only at the end of the input, allowing trailing whitespace.
+fn end(s: &str) -> PResult<'_, ()> {
+    let rest = s.trim_start();
+    if rest.is_empty() {
+        Ok((rest, ()))
+    } else {
+        Err(Failure::new(rest, "end of input"))
+    }
+}
+
+/// Parses exactly `lit`.
+pub fn literal<'a>(lit: &'static str) -> impl Parser<'a, &'a str> {
+    move |s: &'a str| {
+        let s = skip_space(s);
+        match s.strip_prefix(lit) {
+            Some(rest) => Ok((rest, &s[..lit.len()])),
+            None => Err(Failure::new(s, format!("'{}'", lit))),
+        }
+    }
+}
+
+/// Parses the longest of the `options` words that the input starts with.
+pub fn one_of<'a>(options: &'static [&'static str]) -> impl Parser<'a, &'static str> {
+    move |s: &'a str| {
+        let s = skip_space(s);
+        options
+            .iter()
+            .filter(|o| s.starts_with(**o))
+            .max_by_key(|o| o.len())
+            .map(|o| (&s[o.len()..], *o))
+            .filter(|(rest, _)| !starts_with_word_char(rest))
+            .ok_or_else(|| Failure::new(s, quoted(options)))
+    }
+}
+
+/// A fixed set of words for [`keyword`]. The matcher for them is built the first time it's
+/// needed and then shared by every parser made from the set.
+pub struct Keywords {
+    words: &'static [&'static str],
+    matcher: OnceLock<AhoCorasick>,
+}
+impl Keywords {
+    pub const fn new(words: &'static [&'static str]) -> Self {
+        Self {
+            words,
+            matcher: OnceLock::new(),
+        }
+    }
+
+    fn matcher(&self) -> &AhoCorasick {
+        self.matcher.get_or_init(|| AhoCorasick::new(self.words))
+    }
+}
+
+/// Like [`one_of`], but for a `static` set of [`Keywords`], so that a parser that is built over
+/// and over (e.g. once per line) doesn't set up its matcher every time.
+pub fn keyword<'a>(keywords: &'static Keywords) -> impl Parser<'a, &'static str> {
+    move |s: &'a str| {
+        let s = skip_space(s);
+        keywords
+            .matcher()
+            .longest_prefix(s)
+            .map(|m| (&s[m.end..], keywords.words[m.pattern]))
+            .filter(|(rest, _)| !starts_with_word_char(rest))
+            .ok_or_else(|| Failure::new(s, quoted(keywords.words)))
+    }
+}
+
+/// The `options` for an error message, e.g. `'red', 'green', 'blue'`.
+fn quoted(options: &[&str]) -> String {
+    options
+        .iter()
+        .map(|o| format!("'{}'", o))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Parses a run of letters, digits, `-` and `_`, such as `seed-to-soil`.
+pub fn identifier<'a>() -> impl Parser<'a, &'a str> {
+    |s: &'a str| {
+        let s = skip_space(s);
+        let len = s.len() - s.trim_start_matches(is_word_char).len();
+        if len == 0 {
+            Err(Failure::new(s, "an identifier"))
+        } else {
+            Ok((&s[len..], &s[..len]))
+        }
+    }
+}
+
+/// Parses an integer with an optional leading `-` (for signed types only). The number must not
+/// be directly followed by a letter, so `4B` is not read as `4`.
+pub fn integer<'a, T: Integer>() -> impl Parser<'a, T> {
+    |s: &'a str| {
+        let s = skip_space(s);
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(rest) if T::SIGNED => (true, rest),
+            _ => (false, s),
+        };
+        let len = digits.len()
+            - digits
+                .trim_start_matches(|c: char| c.is_ascii_digit())
+                .len();
+        let rest = &digits[len..];
+
+        if len == 0 || starts_with_word_char(rest) {
+            return Err(Failure::new(s, "a number"));
+        }
+
+        digits[..len]
+            .bytes()
+            .try_fold(T::default(), |n, d| n.checked_push(d - b'0', negative))
+            .map(|n| (rest, n))
+            .ok_or_else(|| Failure::new(s, format!("a number that fits in {}", T::NAME)))
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_'
+}
+
+fn starts_with_word_char(s: &str) -> bool {
+    s.starts_with(|c: char| c.is_alphanumeric())
+}
+
+pub fn map<'a, A, B>(parser: impl Parser<'a, A>, f: impl Fn(A) -> B) -> impl Parser<'a, B> {
+    move |s| parser(s).map(|(rest, a)| (rest, f(a)))
+}
+
+pub fn pair<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, (A, B)> {
+    move |s| {
+        let (rest, a) = first(s)?;
+        let (rest, b) = second(rest)?;
+        Ok((rest, (a, b)))
+    }
+}
+
+/// Parses `prefix` and then `parser`, keeping only the latter's value.
+pub fn preceded<'a, A, B>(
+    prefix: impl Parser<'a, A>,
+    parser: impl Parser<'a, B>,
+) -> impl Parser<'a, B> {
+    map(pair(prefix, parser), |(_, b)| b)
+}
+
+/// Parses `parser` and then `suffix`, keeping only the former's value.
+pub fn terminated<'a, A, B>(
+    parser: impl Parser<'a, A>,
+    suffix: impl Parser<'a, B>,
+) -> impl Parser<'a, A> {
+    map(pair(parser, suffix), |(a, _)| a)
+}
+
+/// Parses `parser` zero or more times, until it fails.
+pub fn many<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |mut s| {
+        let mut items = Vec::new();
+        while let Ok((rest, item)) = parser(s) {
+            items.push(item);
+            s = rest;
+        }
+        Ok((s, items))
+    }
+}
+
+/// Parses one or more `parser`s separated by `separator`.
+pub fn separated<'a, T, S>(
+    parser: impl Parser<'a, T>,
+    separator: impl Parser<'a, S>,
+) -> impl Parser<'a, Vec<T>> {
+    move |s| {
+        let (mut s, item) = parser(s)?;
+        let mut items = vec![item];
+        while let Ok((rest, _)) = separator(s) {
+            let (rest, item) = parser(rest)?;
+            items.push(item);
+            s = rest;
+        }
+        Ok((s, items))
+    }
+}
+
+/// Parses a `key: value` header.
+pub fn key_value<'a, K, V>(
+    key: impl Parser<'a, K>,
+    value: impl Parser<'a, V>,
+) -> impl Parser<'a, (K, V)> {
+    pair(terminated(key, literal(":")), value)
+}
+
+/// Parses every non-blank line up to the next blank line (or the end of the input) with
+/// `parser`, which must consume the whole line.
+pub fn lines<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |mut s: &'a str| {
+        let mut items = Vec::new();
+        loop {
+            let (line, rest) = split_line(s);
+            if line.trim().is_empty() {
+                return Ok((s, items));
+            }
+
+            items.push(parse_whole(line, &parser, "end of line")?);
+            s = rest;
+        }
+    }
+}
+
+/// Parses every block of lines separated by one or more blank lines with `parser`, which must
+/// consume the whole block.
+pub fn sections<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |mut s: &'a str| {
+        let mut items = Vec::new();
+        loop {
+            let (block, rest) = split_block(s);
+            if block.is_empty() {
+                return Ok((s, items));
+            }
+
+            items.push(parse_whole(block, &parser, "a blank line")?);
+            s = rest;
+        }
+    }
+}
+
+/// Splits off the first line of `s`, returning it and the input after its line break.
+fn split_line(s: &str) -> (&str, &str) {
+    match s.find('\n') {
+        Some(i) => (&s[..i], &s[i + 1..]),
+        None => (s, &s[s.len()..]),
+    }
+}
+
+/// Skips leading blank lines and splits off the block of non-blank lines that follows.
+pub(crate) fn split_block(mut s: &str) -> (&str, &str) {
+    loop {
+        let (line, rest) = split_line(s);
+        if !line.trim().is_empty() || rest.is_empty() {
+            break;
+        }
+        s = rest;
+    }
+
+    let mut rest = s;
+    while !rest.is_empty() {
+        let (line, next) = split_line(rest);
+        if line.trim().is_empty() {
+            break;
+        }
+        rest = next;
+    }
+
+    (&s[..s.len() - rest.len()], rest)
+}
+
+fn parse_whole<'a, T>(
+    s: &'a str,
+    parser: &impl Parser<'a, T>,
+    expected_end: &'static str,
+) -> Result<T, Failure<'a>> {
+    let (rest, value) = parser(s)?;
+    match end(rest) {
+        Ok(_) => Ok(value),
+        Err(f) => Err(Failure::new(f.rest, expected_end)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_literal() {
+        assert_eq!(literal("Game")("  Game 1"), Ok((" 1", "Game")));
+        assert_eq!(
+            literal("Game")("Card 1"),
+            Err(Failure::new("Card 1", "'Game'"))
+        );
+    }
+
+    #[test]
+    fn test_one_of() {
+        let colour = one_of(&["red", "green", "blue"]);
+
+        assert_eq!(colour(" green,"), Ok((",", "green")));
+        assert!(colour(" greenish").is_err());
+        assert_eq!(
+            one_of(&["red", "reddish"])("reddish brown"),
+            Ok((" brown", "reddish"))
+        );
+        assert_eq!(
+            colour(" purple").unwrap_err().expected,
+            "'red', 'green', 'blue'"
+        );
+    }
+
+    #[test]
+    fn test_keyword() {
+        static COLOURS: Keywords = Keywords::new(&["red", "reddish", "green"]);
+
+        assert_eq!(keyword(&COLOURS)(" green,"), Ok((",", "green")));
+        assert_eq!(
+            keyword(&COLOURS)("reddish brown"),
+            Ok((" brown", "reddish"))
+        );
+        assert!(keyword(&COLOURS)(" greenish").is_err());
+        assert_eq!(
+            keyword(&COLOURS)(" purple").unwrap_err().expected,
+            "'red', 'reddish', 'green'"
+        );
+    }
+
+    #[test]
+    fn test_identifier() {
+        assert_eq!(
+            identifier()("seed-to-soil map:"),
+            Ok((" map:", "seed-to-soil"))
+        );
+        assert!(identifier()(": map").is_err());
+    }
+
+    #[test]
+    fn test_integer() {
+        assert_eq!(integer::<u32>()(" 42 rest"), Ok((" rest", 42)));
+        assert_eq!(integer::<i32>()("-42"), Ok(("", -42)));
+        assert_eq!(
+            integer::<u32>()("-42"),
+            Err(Failure::new("-42", "a number"))
+        );
+        assert_eq!(integer::<u32>()("4B"), Err(Failure::new("4B", "a number")));
+        assert_eq!(
+            integer::<u8>()("256"),
+            Err(Failure::new("256", "a number that fits in u8"))
+        );
+    }
+
+    #[test]
+    fn test_many() {
+        assert_eq!(
+            many(integer::<u32>())(" 1 2  3 | 4"),
+            Ok((" | 4", vec![1, 2, 3]))
+        );
+        assert_eq!(many(integer::<u32>())("| 4"), Ok(("| 4", vec![])));
+    }
+
+    #[test]
+    fn test_separated() {
+        let list = separated(integer::<u32>(), literal(","));
+
+        assert_eq!(list("1, 2,3;"), Ok((";", vec![1, 2, 3])));
+        assert_eq!(list("1, x"), Err(Failure::new("x", "a number")));
+        assert!(list("").is_err());
+    }
+
+    #[test]
+    fn test_key_value() {
+        let header = key_value(literal("seeds"), many(integer::<u64>()));
+
+        assert_eq!(header("seeds: 79 14"), Ok(("", ("seeds", vec![79, 14]))));
+        assert_eq!(header("seeds 79 14"), Err(Failure::new("79 14", "':'")));
+    }
+
+    #[test]
+    fn test_lines() {
+        let rows = lines(many(integer::<u32>()));
+
+        assert_eq!(rows("1 2\n3\n\n4"), Ok(("\n4", vec![vec![1, 2], vec![3]])));
+        assert_eq!(rows("1 2\n3 x\n"), Err(Failure::new("x", "end of line")));
+    }
+
+    #[test]
+    fn test_sections() {
+        let blocks = sections(lines(integer::<u32>()));
+
+        assert_eq!(
+            blocks("\n1\n2\n\n\n3\n"),
+            Ok(("", vec![vec![1, 2], vec![3]]))
+        );
+        assert_eq!(blocks(""), Ok(("", vec![])));
+        assert_eq!(blocks("1\n2 3\n"), Err(Failure::new("3", "end of line")));
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = "seeds: 1 2\n\nsoil: 3 x";
+        let e = parse(
+            input,
+            sections(key_value(identifier(), many(integer::<u32>()))),
+        )
+        .unwrap_err();
+
+        assert_eq!((e.line, e.column), (3, 9));
+        assert_eq!(e.snippet, "soil: 3 x");
+        assert_eq!(e.expected, "a blank line");
+    }
+}